@@ -0,0 +1,195 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the sequence `initial, step(initial), ...` becomes periodic: state `start` is the first
+/// state that repeats, and it repeats every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CycleInfo {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl CycleInfo {
+    /// The index, before the second repetition of the cycle, of the state reached after `n`
+    /// steps.
+    pub fn index_after(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare.  Uses constant memory but calls `step` roughly three times as
+/// often as `find_cycle`.
+pub fn floyd<S, F>(initial: S, mut step: F) -> CycleInfo
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    CycleInfo { start, length }
+}
+
+/// Brent's algorithm.  Uses constant memory and usually fewer calls to `step` than `floyd`.
+pub fn brent<S, F>(initial: S, mut step: F) -> CycleInfo
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    CycleInfo { start, length }
+}
+
+/// A periodic sequence of states, recorded up to the end of its first cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    pub info: CycleInfo,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    pub fn start(&self) -> usize {
+        self.info.start
+    }
+
+    pub fn length(&self) -> usize {
+        self.info.length
+    }
+
+    /// The states before the cycle followed by one full cycle.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state after `n` steps, for any `n`.
+    pub fn state_after(&self, n: usize) -> &S {
+        &self.states[self.info.index_after(n)]
+    }
+}
+
+/// Runs `step` from `initial`, remembering every state, until a state repeats.  Calls `step`
+/// exactly `start + length` times.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return Cycle {
+                info: CycleInfo { start, length },
+                states,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for initial in 0..255 {
+            let expected = find_cycle(initial, next).info;
+            assert_eq!(floyd(initial, next), expected);
+            assert_eq!(brent(initial, next), expected);
+        }
+        let cycle = find_cycle(3, next);
+        assert_eq!(
+            cycle.info,
+            CycleInfo {
+                start: 2,
+                length: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_state_after() {
+        let cycle = find_cycle(3u32, next);
+        let mut state = 3;
+        for n in 0..50 {
+            assert_eq!(*cycle.state_after(n), state);
+            state = next(&state);
+        }
+        assert_eq!(
+            *cycle.state_after(1_000_000_000),
+            cycle.states()[2 + (1_000_000_000 - 2) % 6]
+        );
+    }
+
+    #[test]
+    fn test_grid_cycle() {
+        let grid = Grid::from("#..\n...\n...");
+        let cycle = find_cycle(grid.clone(), |g| g.rotate_clockwise());
+        assert_eq!(
+            cycle.info,
+            CycleInfo {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(cycle.state_after(1_000_000_001), &grid.rotate_clockwise());
+        assert_eq!(brent(grid, |g| g.rotate_clockwise()), cycle.info);
+    }
+}
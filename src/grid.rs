@@ -1,3 +1,37 @@
+mod automaton;
+mod bit_grid;
+mod direction8;
+mod distance;
+mod image;
+mod junction;
+mod lines;
+mod parse;
+mod pattern;
+mod raster;
+mod regions;
+mod render;
+mod resize;
+mod sparse;
+mod transform;
+mod view;
+mod wrapping;
+
+pub use automaton::Automaton;
+pub use bit_grid::{BitGrid, BitNeighbors};
+pub use direction8::{Direction8, DirectionError};
+pub use distance::Nearest;
+pub use image::FrameWriter;
+pub use junction::JunctionGraph;
+pub use lines::Ray;
+pub use parse::{GridParseError, Markers};
+pub use raster::{LinePoints, line_points};
+pub use regions::Region;
+pub use render::GridRenderer;
+pub use sparse::{SparseGrid, SparseNeighbors};
+pub use transform::Orientation;
+pub use view::{GridView, GridViewMut, ViewNeighbors, Windows};
+pub use wrapping::WrappingNeighbors;
+
 use std::{
     fmt::{self, Display, Formatter, Result},
     ops::{
@@ -252,7 +286,42 @@ impl Sub<Vector> for Point {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// The inclusive bounds of a set of points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn include(&mut self, point: Point) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T: Clone + Default + PartialEq> {
     data: Vec<T>,
     pub width: usize,
@@ -599,7 +668,66 @@ impl Iterator for AllNeighborPoints {
     }
 }
 
+/// Which cells count as neighbours: the four orthogonal cells, or all eight surrounding cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    #[default]
+    Orthogonal,
+    All,
+}
+
+const ORTHOGONAL_OFFSETS: [Vector; 4] = [
+    Vector::new(0, -1),
+    Vector::new(1, 0),
+    Vector::new(0, 1),
+    Vector::new(-1, 0),
+];
+
+const ALL_OFFSETS: [Vector; 8] = [
+    Vector::new(0, -1),
+    Vector::new(1, -1),
+    Vector::new(1, 0),
+    Vector::new(1, 1),
+    Vector::new(0, 1),
+    Vector::new(-1, 1),
+    Vector::new(-1, 0),
+    Vector::new(-1, -1),
+];
+
+impl Connectivity {
+    /// The offsets to the neighbours, clockwise from north.
+    pub fn offsets(self) -> &'static [Vector] {
+        match self {
+            Connectivity::Orthogonal => &ORTHOGONAL_OFFSETS,
+            Connectivity::All => &ALL_OFFSETS,
+        }
+    }
+}
+
+pub enum Neighbors<'a, T: Clone + Default + PartialEq> {
+    Orthogonal(OrthogonalNeighbors<'a, T>),
+    All(AllNeighbors<'a, T>),
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for Neighbors<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Neighbors::Orthogonal(iter) => iter.next(),
+            Neighbors::All(iter) => iter.next(),
+        }
+    }
+}
+
 impl<T: Clone + Default + PartialEq> Grid<T> {
+    pub fn neighbors(&'_ self, center: Point, connectivity: Connectivity) -> Neighbors<'_, T> {
+        match connectivity {
+            Connectivity::Orthogonal => Neighbors::Orthogonal(self.orthogonal_neighbors(center)),
+            Connectivity::All => Neighbors::All(self.all_neighbors(center)),
+        }
+    }
+
     pub fn orthogonal_neighbors(&'_ self, center: Point) -> OrthogonalNeighbors<'_, T> {
         OrthogonalNeighbors {
             grid: self,
@@ -729,6 +857,15 @@ impl Direction {
             Turn::Left => self.left(),
         }
     }
+    pub fn try_from_vector(v: Vector) -> Option<Self> {
+        match (v.x, v.y) {
+            (1, 0) => Some(Direction::East),
+            (0, 1) => Some(Direction::South),
+            (-1, 0) => Some(Direction::West),
+            (0, -1) => Some(Direction::North),
+            _ => None,
+        }
+    }
     pub fn try_from_char(c: char) -> Option<Self> {
         match c {
             '>' => Some(Direction::East),
@@ -0,0 +1,161 @@
+use std::mem;
+
+use super::{Connectivity, Grid, Neighbors, Point};
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// Writes the next generation of a cellular automaton into `next`, calling `rule` with each
+    /// cell and its neighbourhood.  `next` is only reallocated if its size differs from this
+    /// grid.  Returns whether any cell changed.
+    pub fn step_into<F>(&self, next: &mut Grid<T>, connectivity: Connectivity, mut rule: F) -> bool
+    where
+        F: FnMut(Point, &T, Neighbors<'_, T>) -> T,
+    {
+        if next.width != self.width || next.height != self.height {
+            *next = self.clone();
+        }
+        let mut changed = false;
+        for (i, (point, value)) in self.iter().enumerate() {
+            let new_value = rule(point, value, self.neighbors(point, connectivity));
+            changed |= new_value != *value;
+            next.data[i] = new_value;
+        }
+        changed
+    }
+
+    /// Advances the grid one generation in place, using `buffer` for the new cells and then
+    /// swapping it with the grid, so repeated steps do not allocate.  Returns whether any cell
+    /// changed.
+    pub fn step<F>(&mut self, buffer: &mut Grid<T>, connectivity: Connectivity, rule: F) -> bool
+    where
+        F: FnMut(Point, &T, Neighbors<'_, T>) -> T,
+    {
+        let changed = self.step_into(buffer, connectivity, rule);
+        mem::swap(self, buffer);
+        changed
+    }
+}
+
+/// A double buffered cellular automaton over a `Grid`.
+#[derive(Debug, Clone)]
+pub struct Automaton<T: Clone + Default + PartialEq> {
+    current: Grid<T>,
+    next: Grid<T>,
+    connectivity: Connectivity,
+    generation: usize,
+}
+
+impl<T: Clone + Default + PartialEq> Automaton<T> {
+    pub fn new(grid: Grid<T>, connectivity: Connectivity) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            connectivity,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// The number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Advances one generation and returns whether any cell changed.
+    pub fn step<F>(&mut self, rule: F) -> bool
+    where
+        F: FnMut(Point, &T, Neighbors<'_, T>) -> T,
+    {
+        self.generation += 1;
+        self.current.step(&mut self.next, self.connectivity, rule)
+    }
+
+    /// Advances `n` generations and returns whether any of them changed a cell.
+    pub fn run_n<F>(&mut self, n: usize, mut rule: F) -> bool
+    where
+        F: FnMut(Point, &T, Neighbors<'_, T>) -> T,
+    {
+        let mut changed = false;
+        for _ in 0..n {
+            changed |= self.step(&mut rule);
+        }
+        changed
+    }
+
+    /// Steps until a generation changes nothing, and returns the number of generations that
+    /// did change the grid.  Never returns if the automaton oscillates.
+    pub fn run_until_stable<F>(&mut self, mut rule: F) -> usize
+    where
+        F: FnMut(Point, &T, Neighbors<'_, T>) -> T,
+    {
+        let mut changes = 0;
+        while self.step(&mut rule) {
+            changes += 1;
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(_: Point, &alive: &bool, neighbors: Neighbors<'_, bool>) -> bool {
+        let count = neighbors.filter(|(_, alive)| **alive).count();
+        count == 3 || (alive && count == 2)
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut grid = Grid::new(5, 5, false);
+        for x in 1..4 {
+            grid[Point::new(x, 2)] = true;
+        }
+        let start = grid.clone();
+        let mut buffer = Grid::new(0, 0, false);
+        assert!(grid.step(&mut buffer, Connectivity::All, life));
+        assert!(grid[Point::new(2, 1)] && grid[Point::new(2, 3)]);
+        assert!(!grid[Point::new(1, 2)]);
+
+        let mut automaton = Automaton::new(start.clone(), Connectivity::All);
+        assert!(automaton.run_n(4, life));
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.grid(), &start);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let seats = Grid::from(
+            "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL",
+        );
+        let mut automaton = Automaton::new(seats, Connectivity::All);
+        let changes = automaton.run_until_stable(|_, &seat, neighbors| {
+            let occupied = neighbors.filter(|(_, c)| **c == b'#').count();
+            match seat {
+                b'L' if occupied == 0 => b'#',
+                b'#' if occupied >= 4 => b'L',
+                other => other,
+            }
+        });
+        assert_eq!(changes, 5);
+        let occupied = automaton.grid().iter().filter(|(_, c)| **c == b'#').count();
+        assert_eq!(occupied, 37);
+        assert!(!automaton.run_n(3, |_, &c, _| c));
+    }
+}
@@ -0,0 +1,369 @@
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+use super::{Connectivity, Direction, Grid, Point, Vector};
+use crate::bit_flags::BitFlags;
+
+const WORD_BITS: usize = 64;
+
+/// A grid of booleans packed one bit per cell.  Each row starts on a fresh word, with column
+/// `x` in bit `x % 64` of word `x / 64`, and bits past the end of a row are always clear, so
+/// whole rows can be combined a word at a time.  Grids combined with `&=`, `|=` and `^=` must
+/// be the same size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<BitFlags<u64>>,
+    words_per_row: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize, value: bool) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        let mut grid = Self {
+            words: vec![BitFlags(if value { u64::MAX } else { 0 }); words_per_row * height],
+            words_per_row,
+            width,
+            height,
+        };
+        grid.clear_padding();
+        grid
+    }
+
+    /// A bit grid with the cells of `grid` that match `predicate` set.
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut predicate: F) -> Self
+    where
+        T: Clone + Default + PartialEq,
+        F: FnMut(&T) -> bool,
+    {
+        let mut result = Self::new(grid.width, grid.height, false);
+        for (p, value) in grid {
+            if predicate(value) {
+                result.set(p, true);
+            }
+        }
+        result
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.width, self.height, false);
+        for p in self.ones() {
+            grid[p] = true;
+        }
+        grid
+    }
+
+    fn clear_padding(&mut self) {
+        let used = self.width % WORD_BITS;
+        if used == 0 {
+            return;
+        }
+        let mask = (1u64 << used) - 1;
+        for row in self.words.chunks_mut(self.words_per_row) {
+            row[self.words_per_row - 1].0 &= mask;
+        }
+    }
+
+    fn word_index(&self, point: Point) -> usize {
+        assert!(self.in_bounds(point), "{point} is outside the grid");
+        point.y * self.words_per_row + point.x / WORD_BITS
+    }
+
+    pub fn get(&self, point: Point) -> bool {
+        self.words[self.word_index(point)].get(point.x % WORD_BITS)
+    }
+
+    pub fn set(&mut self, point: Point, value: bool) {
+        let index = self.word_index(point);
+        self.words[index].set_value(point.x % WORD_BITS, value);
+    }
+
+    pub fn in_bounds(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn add_vector(&self, point: Point, vector: Vector) -> Option<Point> {
+        (point + vector).filter(|p| self.in_bounds(*p))
+    }
+
+    pub fn add_direction(&self, point: Point, direction: Direction) -> Option<Point> {
+        self.add_vector(point, Vector::from_direction(direction))
+    }
+
+    /// Every cell in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, bool)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| {
+                let p = Point::new(x, y);
+                (p, self.get(p))
+            })
+        })
+    }
+
+    /// The set cells in reading order.
+    pub fn ones(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let y = i / self.words_per_row;
+            let x0 = i % self.words_per_row * WORD_BITS;
+            let mut bits = word.0;
+            std::iter::from_fn(move || {
+                let bit = BitFlags(bits).lowest_bit_set()?;
+                bits &= bits - 1;
+                Some(Point::new(x0 + bit, y))
+            })
+        })
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.0.count_ones() as usize).sum()
+    }
+
+    pub fn neighbors(&'_ self, center: Point, connectivity: Connectivity) -> BitNeighbors<'_> {
+        BitNeighbors {
+            grid: self,
+            center,
+            offsets: connectivity.offsets(),
+            current_direction: 0,
+        }
+    }
+
+    pub fn orthogonal_neighbors(&'_ self, center: Point) -> BitNeighbors<'_> {
+        self.neighbors(center, Connectivity::Orthogonal)
+    }
+
+    pub fn all_neighbors(&'_ self, center: Point) -> BitNeighbors<'_> {
+        self.neighbors(center, Connectivity::All)
+    }
+
+    /// The words holding row `y`, for operating on a row 64 cells at a time.
+    pub fn row_words(&self, y: usize) -> &[BitFlags<u64>] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Combines row `y` with `words`, which must be a row of a grid of the same width, by `op`,
+    /// a word at a time, keeping the padding clear.
+    pub fn update_row<F>(&mut self, y: usize, words: &[BitFlags<u64>], mut op: F)
+    where
+        F: FnMut(&mut BitFlags<u64>, BitFlags<u64>),
+    {
+        assert_eq!(words.len(), self.words_per_row);
+        let start = y * self.words_per_row;
+        for (word, &other) in self.words[start..start + self.words_per_row]
+            .iter_mut()
+            .zip(words)
+        {
+            op(word, other);
+        }
+        self.clear_padding();
+    }
+
+    /// Flips every cell.
+    pub fn invert(&mut self) {
+        for word in &mut self.words {
+            *word = !*word;
+        }
+        self.clear_padding();
+    }
+
+    /// A copy with every cell moved by `offset`.  Cells moved off the grid are lost and cells
+    /// moved onto it from outside are clear.
+    pub fn shifted(&self, offset: Vector) -> Self {
+        let mut result = Self::new(self.width, self.height, false);
+        for y in 0..self.height {
+            let Some(source) = y.checked_add_signed(-offset.y).filter(|&s| s < self.height) else {
+                continue;
+            };
+            let start = y * self.words_per_row;
+            shift_row(
+                self.row_words(source),
+                &mut result.words[start..start + self.words_per_row],
+                offset.x,
+            );
+        }
+        result.clear_padding();
+        result
+    }
+}
+
+/// Writes `source` moved `dx` columns to the right into `target`.
+fn shift_row(source: &[BitFlags<u64>], target: &mut [BitFlags<u64>], dx: isize) {
+    let words = dx.unsigned_abs() / WORD_BITS;
+    let bits = dx.unsigned_abs() % WORD_BITS;
+    let word = |i: Option<usize>| i.and_then(|i| source.get(i)).map_or(0, |w| w.0);
+    for (i, target) in target.iter_mut().enumerate() {
+        let (main, carry) = if dx >= 0 {
+            let main = word(i.checked_sub(words));
+            let carry = word(i.checked_sub(words + 1));
+            (main << bits, carry.checked_shr((WORD_BITS - bits) as u32))
+        } else {
+            let main = word(Some(i + words));
+            let carry = word(Some(i + words + 1));
+            (main >> bits, carry.checked_shl((WORD_BITS - bits) as u32))
+        };
+        target.0 = main | carry.unwrap_or(0);
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        for (word, other) in self.words.iter_mut().zip(&rhs.words) {
+            *word &= *other;
+        }
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        for (word, other) in self.words.iter_mut().zip(&rhs.words) {
+            *word |= *other;
+        }
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        for (word, other) in self.words.iter_mut().zip(&rhs.words) {
+            *word ^= *other;
+        }
+    }
+}
+
+pub struct BitNeighbors<'a> {
+    grid: &'a BitGrid,
+    center: Point,
+    offsets: &'static [Vector],
+    current_direction: usize,
+}
+
+impl Iterator for BitNeighbors<'_> {
+    type Item = (Point, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(offset) = self.offsets.get(self.current_direction) {
+            self.current_direction += 1;
+            if let Some(p) = self.grid.add_vector(self.center, *offset) {
+                return Some((p, self.grid.get(p)));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARDEN: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_step_counter() {
+        let garden = Grid::from(GARDEN);
+        let open = BitGrid::from_grid(&garden, |&c| c != b'#');
+        let mut reached = BitGrid::new(garden.width, garden.height, false);
+        reached.set(garden.find(b'S').unwrap(), true);
+        for _ in 0..6 {
+            let mut next = reached.shifted(Vector::new(1, 0));
+            for offset in [Vector::new(-1, 0), Vector::new(0, 1), Vector::new(0, -1)] {
+                next |= &reached.shifted(offset);
+            }
+            next &= &open;
+            reached = next;
+        }
+        assert_eq!(reached.count_ones(), 16);
+    }
+
+    #[test]
+    fn test_wide_rows() {
+        let mut grid = BitGrid::new(150, 3, false);
+        for x in [0, 63, 64, 100, 149] {
+            grid.set(Point::new(x, 1), true);
+        }
+        assert_eq!(grid.row_words(1).len(), 3);
+        let expected = |dx: isize, dy: isize| -> Vec<Point> {
+            grid.ones()
+                .filter_map(|p| grid.add_vector(p, Vector::new(dx, dy)))
+                .collect()
+        };
+        for (dx, dy) in [
+            (1, 0),
+            (-1, 1),
+            (64, 0),
+            (-65, -1),
+            (86, 0),
+            (-150, 0),
+            (0, 2),
+        ] {
+            let shifted = grid.shifted(Vector::new(dx, dy));
+            assert_eq!(shifted.ones().collect::<Vec<_>>(), expected(dx, dy));
+        }
+
+        let mut inverted = grid.clone();
+        inverted.invert();
+        assert_eq!(inverted.count_ones(), 450 - 5);
+        inverted ^= &BitGrid::new(150, 3, true);
+        assert_eq!(inverted, grid);
+
+        let row = grid.row_words(1).to_vec();
+        grid.update_row(0, &row, |word, other| *word |= other);
+        assert_eq!(grid.count_ones(), 10);
+        assert!(grid.get(Point::new(149, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn test_out_of_bounds() {
+        let mut grid = BitGrid::new(10, 2, false);
+        grid.set(Point::new(12, 0), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_sizes() {
+        let mut grid = BitGrid::new(10, 2, true);
+        grid &= &BitGrid::new(2, 10, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_row() {
+        let mut grid = BitGrid::new(100, 2, false);
+        let row = BitGrid::new(10, 1, true).row_words(0).to_vec();
+        grid.update_row(0, &row, |word, other| *word |= other);
+    }
+
+    #[test]
+    fn test_point_api() {
+        let grid = BitGrid::from_grid(&Grid::from("#.#\n.#.\n#.#"), |&c| c == b'#');
+        let bools = grid.to_grid();
+        assert!(bools[Point::new(2, 2)] && !bools[Point::new(1, 0)]);
+        assert_eq!(BitGrid::from_grid(&bools, |&b| b), grid);
+        let centre = Point::new(1, 1);
+        assert_eq!(
+            grid.orthogonal_neighbors(centre)
+                .filter(|(_, b)| *b)
+                .count(),
+            0
+        );
+        assert_eq!(grid.all_neighbors(centre).filter(|(_, b)| *b).count(), 4);
+        assert_eq!(
+            grid.neighbors(Point::new(0, 0), Connectivity::All).count(),
+            3
+        );
+        assert_eq!(grid.add_direction(Point::new(0, 0), Direction::North), None);
+        assert_eq!(grid.iter().filter(|(_, b)| *b).count(), 5);
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use num_traits::{ConstOne, ConstZero, Num, Signed};
+use thiserror::Error;
+
+use super::{Direction, Vector};
+use crate::pos::Position;
+
+/// The eight compass directions, clockwise from north.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DirectionError {
+    #[error("{0:?} is not a direction")]
+    Parse(String),
+    #[error("{0:?} has no orthogonal equivalent")]
+    NotOrthogonal(Direction8),
+    #[error("Wait has no compass equivalent")]
+    Wait,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Rotates clockwise by `steps` multiples of 45°; negative steps rotate anticlockwise.
+    pub fn rotate(self, steps: i32) -> Self {
+        Self::ALL[(self as i32 + steps).rem_euclid(8) as usize]
+    }
+
+    pub fn rotate_clockwise(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn rotate_anticlockwise(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub const fn to_vector(self) -> Vector {
+        match self {
+            Direction8::North => Vector::new(0, -1),
+            Direction8::NorthEast => Vector::new(1, -1),
+            Direction8::East => Vector::new(1, 0),
+            Direction8::SouthEast => Vector::new(1, 1),
+            Direction8::South => Vector::new(0, 1),
+            Direction8::SouthWest => Vector::new(-1, 1),
+            Direction8::West => Vector::new(-1, 0),
+            Direction8::NorthWest => Vector::new(-1, -1),
+        }
+    }
+
+    pub fn to_position<T: Num + Signed + Copy + PartialOrd + ConstOne + ConstZero>(
+        &self,
+    ) -> Position<T> {
+        let unit = |n: isize| match n {
+            1 => T::ONE,
+            -1 => -T::ONE,
+            _ => T::ZERO,
+        };
+        let v = self.to_vector();
+        Position::new(unit(v.x), unit(v.y))
+    }
+
+    /// Accepts the arrows `^>v<`, the unicode arrows `↑↗→↘↓↙←↖`, and the letters `UDLR` and
+    /// `NESW` in either case.
+    pub fn try_from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | '↑' | 'U' | 'u' | 'N' | 'n' => Some(Direction8::North),
+            '↗' => Some(Direction8::NorthEast),
+            '>' | '→' | 'R' | 'r' | 'E' | 'e' => Some(Direction8::East),
+            '↘' => Some(Direction8::SouthEast),
+            'v' | '↓' | 'D' | 'd' | 'S' | 's' => Some(Direction8::South),
+            '↙' => Some(Direction8::SouthWest),
+            '<' | '←' | 'L' | 'l' | 'W' | 'w' => Some(Direction8::West),
+            '↖' => Some(Direction8::NorthWest),
+            _ => None,
+        }
+    }
+}
+
+/// Parses anything `try_from_char` accepts, or a pair of letters combining a vertical and a
+/// horizontal direction such as `NE`, `sw` or `UL`.
+impl FromStr for Direction8 {
+    type Err = DirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DirectionError::Parse(String::from(s));
+        let mut chars = s.chars();
+        let first = chars
+            .next()
+            .and_then(Direction8::try_from_char)
+            .ok_or_else(error)?;
+        let Some(second) = chars.next() else {
+            return Ok(first);
+        };
+        let second = Direction8::try_from_char(second).ok_or_else(error)?;
+        if chars.next().is_some() {
+            return Err(error());
+        }
+        match (first, second) {
+            (Direction8::North, Direction8::East) => Ok(Direction8::NorthEast),
+            (Direction8::South, Direction8::East) => Ok(Direction8::SouthEast),
+            (Direction8::South, Direction8::West) => Ok(Direction8::SouthWest),
+            (Direction8::North, Direction8::West) => Ok(Direction8::NorthWest),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl From<Direction8> for char {
+    fn from(d: Direction8) -> Self {
+        match d {
+            Direction8::North => '↑',
+            Direction8::NorthEast => '↗',
+            Direction8::East => '→',
+            Direction8::SouthEast => '↘',
+            Direction8::South => '↓',
+            Direction8::SouthWest => '↙',
+            Direction8::West => '←',
+            Direction8::NorthWest => '↖',
+        }
+    }
+}
+
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(d: Direction8) -> Self {
+        d.to_vector()
+    }
+}
+
+impl<T: Num + Signed + Copy + PartialOrd + ConstOne + ConstZero> From<Direction8> for Position<T> {
+    fn from(d: Direction8) -> Self {
+        d.to_position()
+    }
+}
+
+impl TryFrom<Direction> for Direction8 {
+    type Error = DirectionError;
+
+    fn try_from(d: Direction) -> Result<Self, Self::Error> {
+        match d {
+            Direction::North => Ok(Direction8::North),
+            Direction::East => Ok(Direction8::East),
+            Direction::South => Ok(Direction8::South),
+            Direction::West => Ok(Direction8::West),
+            Direction::Wait => Err(DirectionError::Wait),
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = DirectionError;
+
+    fn try_from(d: Direction8) -> Result<Self, Self::Error> {
+        match d {
+            Direction8::North => Ok(Direction::North),
+            Direction8::East => Ok(Direction::East),
+            Direction8::South => Ok(Direction::South),
+            Direction8::West => Ok(Direction::West),
+            diagonal => Err(DirectionError::NotOrthogonal(diagonal)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos;
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(Direction8::North.rotate_clockwise(), Direction8::NorthEast);
+        assert_eq!(
+            Direction8::North.rotate_anticlockwise(),
+            Direction8::NorthWest
+        );
+        assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+        assert_eq!(Direction8::East.rotate(-10), Direction8::North);
+        for d in Direction8::ALL {
+            assert_eq!(
+                d.rotate(2).to_vector(),
+                Vector::new(-d.to_vector().y, d.to_vector().x)
+            );
+            assert_eq!(d.is_diagonal(), d.to_vector().manhattan() == 2);
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Vector::from(Direction8::SouthWest), Vector::new(-1, 1));
+        assert_eq!(
+            Position::<i32>::from(Direction8::NorthEast),
+            Position::new(1, -1)
+        );
+        assert_eq!(Direction8::try_from(Direction::West), Ok(Direction8::West));
+        assert_eq!(Direction::try_from(Direction8::South), Ok(Direction::South));
+        assert!(Direction::try_from(Direction8::NorthWest).is_err());
+        assert!(Direction8::try_from(Direction::Wait).is_err());
+        for d in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            let p: pos::Direction = d.into();
+            assert_eq!(Direction::from(p), d);
+            assert_eq!(
+                p.to_position::<isize>(),
+                Position::from(Vector::from_direction(d))
+            );
+        }
+        assert_eq!(Direction::from(pos::Direction::Wait), Direction::Wait);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("U".parse(), Ok(Direction8::North));
+        assert_eq!("w".parse(), Ok(Direction8::West));
+        assert_eq!("v".parse(), Ok(Direction8::South));
+        assert_eq!("↘".parse(), Ok(Direction8::SouthEast));
+        assert_eq!("NE".parse(), Ok(Direction8::NorthEast));
+        assert_eq!("sw".parse(), Ok(Direction8::SouthWest));
+        assert_eq!("UL".parse(), Ok(Direction8::NorthWest));
+        assert!("EN".parse::<Direction8>().is_err());
+        assert!("NS".parse::<Direction8>().is_err());
+        assert!("NEE".parse::<Direction8>().is_err());
+        assert!("".parse::<Direction8>().is_err());
+        assert!("x".parse::<Direction8>().is_err());
+    }
+}
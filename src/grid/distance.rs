@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+
+use super::{Grid, Point};
+
+/// The closest source to a cell in a Voronoi labelling, as produced by `Grid::nearest_sources`.
+/// `source` is the index of the source in the order given, or `None` if two or more sources
+/// are equally close.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Nearest {
+    pub source: Option<usize>,
+    pub distance: u32,
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// The number of orthogonal steps from the nearest of `sources` to every cell, moving only
+    /// through cells that are `passable`.  Cells that cannot be reached are `None`.  Sources are
+    /// at distance 0 whether or not they are passable.
+    pub fn distance_map<F>(
+        &self,
+        sources: impl IntoIterator<Item = Point>,
+        passable: F,
+    ) -> Grid<Option<u32>>
+    where
+        F: FnMut(Point, &T) -> bool,
+    {
+        let nearest = self.nearest_sources(sources, passable);
+        let data = nearest
+            .data
+            .into_iter()
+            .map(|cell| cell.map(|n| n.distance))
+            .collect();
+        Grid {
+            data,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// A multi-source breadth first search like `distance_map` that also records which source
+    /// each cell is closest to.
+    pub fn nearest_sources<F>(
+        &self,
+        sources: impl IntoIterator<Item = Point>,
+        mut passable: F,
+    ) -> Grid<Option<Nearest>>
+    where
+        F: FnMut(Point, &T) -> bool,
+    {
+        let mut nearest: Grid<Option<Nearest>> = Grid::new(self.width, self.height, None);
+        let mut queue = VecDeque::new();
+        for (id, source) in sources.into_iter().enumerate() {
+            match &mut nearest[source] {
+                Some(cell) => cell.source = None,
+                cell => {
+                    *cell = Some(Nearest {
+                        source: Some(id),
+                        distance: 0,
+                    });
+                    queue.push_back(source);
+                }
+            }
+        }
+        // Every update to a cell at distance d + 1 happens while cells at distance d are being
+        // taken from the queue, so a cell's label is final before it is itself taken.
+        while let Some(current) = queue.pop_front() {
+            let Nearest { source, distance } = nearest[current].unwrap();
+            for (p, value) in self.orthogonal_neighbors(current) {
+                match &mut nearest[p] {
+                    None if passable(p, value) => {
+                        nearest[p] = Some(Nearest {
+                            source,
+                            distance: distance + 1,
+                        });
+                        queue.push_back(p);
+                    }
+                    Some(cell) if cell.distance == distance + 1 && cell.source != source => {
+                        cell.source = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        nearest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACK: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn test_cheats() {
+        let track = Grid::from(TRACK);
+        let open = |_: Point, c: &u8| *c != b'#';
+        let start = track.find(b'S').unwrap();
+        let end = track.find(b'E').unwrap();
+        let from_start = track.distance_map([start], open);
+        let from_end = track.distance_map([end], open);
+        let length = from_start[end].unwrap();
+        assert_eq!(length, 84);
+        assert_eq!(from_end[start], Some(84));
+        assert_eq!(from_start[Point::new(0, 0)], None);
+
+        let mut good_cheats = 0;
+        for (p, before) in from_start.iter() {
+            let Some(before) = before else { continue };
+            for (q, after) in from_end.iter() {
+                if let Some(after) = after
+                    && p.manhattan(&q) == 2
+                    && before + 2 + after + 20 <= length
+                {
+                    good_cheats += 1;
+                }
+            }
+        }
+        assert_eq!(good_cheats, 5);
+    }
+
+    #[test]
+    fn test_voronoi() {
+        let grid = Grid::new(10, 10, ());
+        let sources = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)].map(Point::from);
+        let nearest = grid.nearest_sources(sources, |_, _| true);
+        let area = |id| {
+            nearest
+                .iter()
+                .filter(|(_, n)| n.unwrap().source == Some(id))
+                .count()
+        };
+        assert_eq!(area(3), 9);
+        assert_eq!(area(4), 17);
+        assert_eq!(
+            nearest[Point::new(0, 4)],
+            Some(Nearest {
+                source: None,
+                distance: 3
+            })
+        );
+        assert_eq!(nearest[Point::new(5, 2)].unwrap().distance, 3);
+
+        let walled = Grid::from("..#..\n..#..");
+        let sources = [Point::new(0, 0), Point::new(0, 0), Point::new(4, 1)];
+        let nearest = walled.nearest_sources(sources, |_, c| *c == b'.');
+        assert_eq!(nearest[Point::new(1, 1)].unwrap().source, None);
+        assert_eq!(nearest[Point::new(3, 0)].unwrap().source, Some(2));
+        assert_eq!(nearest[Point::new(2, 0)], None);
+    }
+}
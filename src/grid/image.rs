@@ -0,0 +1,172 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{Grid, Point};
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    fn write_netpbm<W: Write, const N: usize>(
+        &self,
+        mut writer: W,
+        magic: &str,
+        scale: usize,
+        pixel: impl Fn(&T) -> [u8; N],
+    ) -> io::Result<()> {
+        assert!(scale > 0, "scale must be at least one");
+        write!(
+            writer,
+            "{magic}\n{} {}\n255\n",
+            self.width * scale,
+            self.height * scale
+        )?;
+        let mut row = Vec::with_capacity(self.width * scale * N);
+        for y in 0..self.height {
+            row.clear();
+            for x in 0..self.width {
+                let bytes = pixel(&self[Point::new(x, y)]);
+                for _ in 0..scale {
+                    row.extend_from_slice(&bytes);
+                }
+            }
+            for _ in 0..scale {
+                writer.write_all(&row)?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Writes the grid as a binary PPM image, one `scale` by `scale` square of pixels per cell
+    /// coloured by `colour`.
+    pub fn write_ppm<W: Write, F: Fn(&T) -> [u8; 3]>(
+        &self,
+        writer: W,
+        scale: usize,
+        colour: F,
+    ) -> io::Result<()> {
+        self.write_netpbm(writer, "P6", scale, colour)
+    }
+
+    /// Writes the grid as a binary PGM (greyscale) image, one `scale` by `scale` square of
+    /// pixels per cell shaded by `shade`.
+    pub fn write_pgm<W: Write, F: Fn(&T) -> u8>(
+        &self,
+        writer: W,
+        scale: usize,
+        shade: F,
+    ) -> io::Result<()> {
+        self.write_netpbm(writer, "P5", scale, |value| [shade(value)])
+    }
+
+    pub fn save_ppm<F: Fn(&T) -> [u8; 3]>(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        colour: F,
+    ) -> io::Result<()> {
+        self.write_ppm(BufWriter::new(File::create(path)?), scale, colour)
+    }
+
+    pub fn save_pgm<F: Fn(&T) -> u8>(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        shade: F,
+    ) -> io::Result<()> {
+        self.write_pgm(BufWriter::new(File::create(path)?), scale, shade)
+    }
+}
+
+/// Writes successive states of a simulation as numbered PPM files, `prefix000000.ppm`,
+/// `prefix000001.ppm` and so on, ready to be joined into a video with e.g.
+/// `ffmpeg -i prefix%06d.ppm out.mp4`.
+#[derive(Debug, Clone)]
+pub struct FrameWriter {
+    directory: PathBuf,
+    prefix: String,
+    scale: usize,
+    next_frame: usize,
+}
+
+impl FrameWriter {
+    /// Creates `directory` if it does not exist.
+    pub fn new(directory: impl Into<PathBuf>, prefix: &str) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            prefix: String::from(prefix),
+            scale: 1,
+            next_frame: 0,
+        })
+    }
+
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Writes the next frame and returns its path.
+    pub fn write_frame<T, F>(&mut self, grid: &Grid<T>, colour: F) -> io::Result<PathBuf>
+    where
+        T: Clone + Default + PartialEq,
+        F: Fn(&T) -> [u8; 3],
+    {
+        let path = self
+            .directory
+            .join(format!("{}{:06}.ppm", self.prefix, self.next_frame));
+        grid.save_ppm(&path, self.scale, colour)?;
+        self.next_frame += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_pgm() {
+        let grid = Grid::from("#.\n.#");
+        let mut bytes = Vec::new();
+        grid.write_pgm(&mut bytes, 1, |&c| if c == b'#' { 0 } else { 255 })
+            .unwrap();
+        assert_eq!(bytes, b"P5\n2 2\n255\n\x00\xff\xff\x00");
+    }
+
+    #[test]
+    fn test_write_ppm_scaled() {
+        let grid = Grid::from(vec![vec![1u8, 2]]);
+        let mut bytes = Vec::new();
+        grid.write_ppm(&mut bytes, 2, |&v| [v, 0, 0]).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&bytes[..header.len()], header);
+        let row = [1, 0, 0, 1, 0, 0, 2, 0, 0, 2, 0, 0];
+        assert_eq!(&bytes[header.len()..], [row, row].concat());
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let directory = std::env::temp_dir().join(format!("grid_frames_{}", std::process::id()));
+        let mut frames = FrameWriter::new(&directory, "life_").unwrap().with_scale(3);
+        let mut grid = Grid::new(2, 2, false);
+        for step in 0..3 {
+            grid[Point::new(step % 2, 0)] = true;
+            let path = frames
+                .write_frame(&grid, |&alive| if alive { [255; 3] } else { [0; 3] })
+                .unwrap();
+            assert_eq!(
+                path.file_name().unwrap(),
+                format!("life_{step:06}.ppm").as_str()
+            );
+            assert_eq!(fs::read(&path).unwrap().len(), 11 + 6 * 6 * 3);
+        }
+        assert_eq!(frames.frames_written(), 3);
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+use super::{Direction, Grid, Point};
+
+/// A maze with its corridors contracted into weighted edges between junctions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JunctionGraph {
+    /// The cell of each node; node ids index this `Vec`.
+    pub nodes: Vec<Point>,
+    /// For each node, the `(node, length)` of each corridor leading from it.
+    pub edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl JunctionGraph {
+    pub fn node_id(&self, point: Point) -> Option<usize> {
+        self.nodes.iter().position(|&p| p == point)
+    }
+
+    pub fn edges_from(&self, node: usize) -> &[(usize, usize)] {
+        &self.edges[node]
+    }
+
+    /// The length of the longest path from `from` to `to` that visits no node twice, by
+    /// exhaustive search, or `None` if `to` cannot be reached.
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<usize> {
+        fn search(
+            graph: &JunctionGraph,
+            node: usize,
+            to: usize,
+            visited: &mut [bool],
+        ) -> Option<usize> {
+            if node == to {
+                return Some(0);
+            }
+            visited[node] = true;
+            let mut best = None;
+            for &(next, length) in &graph.edges[node] {
+                if !visited[next]
+                    && let Some(rest) = search(graph, next, to, visited)
+                {
+                    best = best.max(Some(length + rest));
+                }
+            }
+            visited[node] = false;
+            best
+        }
+        search(self, from, to, &mut vec![false; self.nodes.len()])
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// Contracts the corridors of a maze into a graph.  The nodes are `extra_nodes`, such as
+    /// the start and end, in the order given, followed by every passable cell with more than
+    /// two passable orthogonal neighbours, in reading order.  Corridors that dead end without
+    /// reaching a node are dropped.
+    pub fn to_junction_graph<F>(
+        &self,
+        passable: F,
+        extra_nodes: impl IntoIterator<Item = Point>,
+    ) -> JunctionGraph
+    where
+        F: FnMut(Point, &T) -> bool,
+    {
+        self.to_directed_junction_graph(passable, extra_nodes, |_| None)
+    }
+
+    /// Like `to_junction_graph`, but cells for which `one_way` gives a direction can only be
+    /// left in that direction and cannot be entered moving against it, as with the slopes
+    /// `^>v<` of a hiking map.  Edges are then only recorded in the direction they can be
+    /// walked.
+    pub fn to_directed_junction_graph<F, G>(
+        &self,
+        mut passable: F,
+        extra_nodes: impl IntoIterator<Item = Point>,
+        mut one_way: G,
+    ) -> JunctionGraph
+    where
+        F: FnMut(Point, &T) -> bool,
+        G: FnMut(&T) -> Option<Direction>,
+    {
+        let open = Grid {
+            data: self.iter().map(|(p, value)| passable(p, value)).collect(),
+            width: self.width,
+            height: self.height,
+        };
+        let arrows = Grid {
+            data: self.data.iter().map(&mut one_way).collect(),
+            width: self.width,
+            height: self.height,
+        };
+        let can_move = |from: Point, direction: Direction| -> Option<Point> {
+            let to = open.add_direction(from, direction).filter(|&p| open[p])?;
+            let leaves = arrows[from].is_none_or(|d| d == direction);
+            let enters = arrows[to].is_none_or(|d| d != direction.reverse());
+            (leaves && enters).then_some(to)
+        };
+
+        let mut node_ids = Grid::new(self.width, self.height, None);
+        let mut nodes = Vec::new();
+        let junctions = open.iter().filter(|&(p, &is_open)| {
+            is_open && open.orthogonal_neighbors(p).filter(|(_, o)| **o).count() > 2
+        });
+        for p in extra_nodes.into_iter().chain(junctions.map(|(p, _)| p)) {
+            if node_ids[p].is_none() {
+                node_ids[p] = Some(nodes.len());
+                nodes.push(p);
+            }
+        }
+
+        let directions = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ];
+        let mut edges = vec![Vec::new(); nodes.len()];
+        for (id, &node) in nodes.iter().enumerate() {
+            for first in directions.iter().filter_map(|&d| can_move(node, d)) {
+                let (mut previous, mut current, mut length) = (node, first, 1);
+                let end = loop {
+                    if let Some(end) = node_ids[current] {
+                        break Some(end);
+                    }
+                    let mut next = directions
+                        .iter()
+                        .filter_map(|&d| can_move(current, d))
+                        .filter(|&p| p != previous);
+                    match next.next() {
+                        Some(p) => (previous, current, length) = (current, p, length + 1),
+                        None => break None,
+                    }
+                };
+                if let Some(end) = end.filter(|&end| end != id) {
+                    edges[id].push((end, length));
+                }
+            }
+        }
+        JunctionGraph { nodes, edges }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRAILS: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_longest_hike() {
+        let map = Grid::from(TRAILS);
+        let start = Point::new(1, 0);
+        let end = Point::new(map.width - 2, map.height - 1);
+        let passable = |_: Point, c: &u8| *c != b'#';
+
+        let slippery = map.to_directed_junction_graph(passable, [start, end], |&c| {
+            Direction::try_from_char(c as char)
+        });
+        assert_eq!(slippery.nodes.len(), 9);
+        assert_eq!(slippery.longest_path(0, 1), Some(94));
+        assert_eq!(slippery.longest_path(1, 0), None);
+
+        let dry = map.to_junction_graph(passable, [start, end]);
+        assert_eq!(dry.longest_path(0, 1), Some(154));
+        let edges: usize = dry.edges.iter().map(Vec::len).sum();
+        assert_eq!(edges, 2 * 12);
+        assert_eq!(dry.edges_from(0).len(), 1);
+        assert_eq!(dry.node_id(end), Some(1));
+    }
+
+    #[test]
+    fn test_corridor_lengths() {
+        let maze = Grid::from("#######\n#.....#\n#.#.#.#\n#.....#\n#######");
+        let graph = maze.to_junction_graph(|_, c| *c == b'.', []);
+        assert_eq!(graph.nodes, [Point::new(3, 1), Point::new(3, 3)]);
+        let mut lengths: Vec<_> = graph.edges_from(0).iter().map(|&(_, l)| l).collect();
+        lengths.sort();
+        assert_eq!(lengths, [2, 6, 6]);
+        assert!(graph.edges_from(1).iter().all(|&(to, _)| to == 0));
+    }
+}
@@ -0,0 +1,140 @@
+use super::{Grid, Point, Vector};
+
+/// The cells on a straight line through a grid, from a first point in steps of a fixed vector
+/// until the line leaves the grid.
+pub struct Ray<'a, T: Clone + Default + PartialEq> {
+    grid: &'a Grid<T>,
+    next: Option<Point>,
+    step: Vector,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = self.grid.add_vector(current, self.step);
+        Some((current, &self.grid[current]))
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    fn line_from(&'_ self, first: Point, step: Vector) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            next: Some(first).filter(|p| self.in_bounds(*p)),
+            step,
+        }
+    }
+
+    /// The cells seen looking from `start` in steps of `step`, not including `start` itself.
+    pub fn ray(&'_ self, start: Point, step: Vector) -> Ray<'_, T> {
+        assert!(step != Vector::new(0, 0), "a ray needs a non-zero step");
+        Ray {
+            grid: self,
+            next: self.add_vector(start, step),
+            step,
+        }
+    }
+
+    pub fn row(&'_ self, y: usize) -> Ray<'_, T> {
+        self.line_from(Point::new(0, y), Vector::new(1, 0))
+    }
+
+    pub fn column(&'_ self, x: usize) -> Ray<'_, T> {
+        self.line_from(Point::new(x, 0), Vector::new(0, 1))
+    }
+
+    pub fn rows(&'_ self) -> impl Iterator<Item = Ray<'_, T>> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&'_ self) -> impl Iterator<Item = Ray<'_, T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals running down and to the right, starting with the one through the bottom
+    /// left corner and ending with the one through the top right corner.
+    pub fn diagonals(&'_ self) -> impl Iterator<Item = Ray<'_, T>> {
+        let rows = if self.width == 0 { 0 } else { self.height };
+        let left = (0..rows).rev().map(|y| Point::new(0, y));
+        let top = (1..self.width).map(|x| Point::new(x, 0));
+        left.chain(top)
+            .map(|first| self.line_from(first, Vector::new(1, 1)))
+    }
+
+    /// The diagonals running down and to the left, starting with the one through the top left
+    /// corner and ending with the one through the bottom right corner.
+    pub fn anti_diagonals(&'_ self) -> impl Iterator<Item = Ray<'_, T>> {
+        let top = (0..self.width).map(|x| Point::new(x, 0));
+        let right = self
+            .width
+            .checked_sub(1)
+            .into_iter()
+            .flat_map(|x| (1..self.height).map(move |y| Point::new(x, y)));
+        top.chain(right)
+            .map(|first| self.line_from(first, Vector::new(-1, 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text<'a>(line: impl Iterator<Item = (Point, &'a u8)>) -> String {
+        line.map(|(_, &c)| c as char).collect()
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from("abc\ndef");
+        assert_eq!(text(grid.row(1)), "def");
+        assert_eq!(text(grid.column(2)), "cf");
+        assert_eq!(grid.rows().map(text).collect::<Vec<_>>(), ["abc", "def"]);
+        assert_eq!(
+            grid.columns().map(text).collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(grid.row(5).count(), 0);
+        assert_eq!(
+            grid.column(1).map(|(p, _)| p).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = Grid::from("abc\ndef");
+        assert_eq!(
+            grid.diagonals().map(text).collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(text).collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+        let cells: usize = grid.diagonals().map(|d| d.count()).sum();
+        assert_eq!(cells, 6);
+
+        let mut narrow = Grid::from("a\nb\nc");
+        narrow.remove_column(0);
+        assert_eq!(narrow.anti_diagonals().count(), 0);
+        assert_eq!(narrow.diagonals().count(), 0);
+        assert_eq!(narrow.rows().map(|r| r.count()).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::from("30373\n25512\n65332\n33549\n35390");
+        let from = Point::new(2, 2);
+        assert_eq!(text(grid.ray(from, Vector::new(0, -1))), "53");
+        assert_eq!(text(grid.ray(from, Vector::new(1, 1))), "40");
+        assert_eq!(text(grid.ray(from, Vector::new(-2, 0))), "6");
+        assert_eq!(grid.ray(Point::new(4, 4), Vector::new(1, 0)).count(), 0);
+
+        let visible_west = grid
+            .ray(Point::new(3, 1), Vector::new(-1, 0))
+            .all(|(_, &h)| h < grid[Point::new(3, 1)]);
+        assert!(!visible_west);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use super::{Grid, Point};
+
+/// The points of each marker character found by `Grid::parse_with_markers`.
+pub type Markers = HashMap<char, Vec<Point>>;
+
+/// Errors from `Grid::parse`.  Lines and columns are counted from one, in characters.
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E: std::fmt::Debug + std::fmt::Display> {
+    #[error("the input has no rows")]
+    #[diagnostic(code(grid::parse::empty))]
+    Empty,
+    #[error("line {line} has {found} columns, expected {expected}")]
+    #[diagnostic(
+        code(grid::parse::ragged),
+        help("every row of a grid must be the same length")
+    )]
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid cell {character:?} at line {line}, column {column}: {error}")]
+    #[diagnostic(code(grid::parse::cell))]
+    Cell {
+        line: usize,
+        column: usize,
+        character: char,
+        error: E,
+    },
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// Parses one row per line, mapping each character to a cell with `cell`.
+    pub fn parse<E, F>(s: &str, cell: F) -> Result<Self, GridParseError<E>>
+    where
+        E: std::fmt::Debug + std::fmt::Display,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        Self::parse_with_markers(s, &[], cell).map(|(grid, _)| grid)
+    }
+
+    /// As `parse`, but also returns the points of every occurrence of each of the `markers`
+    /// characters.  Marker characters are still passed to `cell`, which decides what the grid
+    /// holds at those points.
+    pub fn parse_with_markers<E, F>(
+        s: &str,
+        markers: &[char],
+        mut cell: F,
+    ) -> Result<(Self, Markers), GridParseError<E>>
+    where
+        E: std::fmt::Debug + std::fmt::Display,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut found = Markers::new();
+        let mut data = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in s.lines().enumerate() {
+            let mut columns = 0;
+            for (x, character) in line.chars().enumerate() {
+                let value = cell(character).map_err(|error| GridParseError::Cell {
+                    line: y + 1,
+                    column: x + 1,
+                    character,
+                    error,
+                })?;
+                if markers.contains(&character) {
+                    found.entry(character).or_default().push(Point::new(x, y));
+                }
+                data.push(value);
+                columns += 1;
+            }
+            match width {
+                None => width = Some(columns),
+                Some(expected) if expected != columns => {
+                    return Err(GridParseError::Ragged {
+                        line: y + 1,
+                        expected,
+                        found: columns,
+                    });
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+        let width = width.ok_or(GridParseError::Empty)?;
+        Ok((
+            Self {
+                data,
+                width,
+                height,
+            },
+            found,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    enum Tile {
+        #[default]
+        Open,
+        Wall,
+    }
+
+    fn tile(c: char) -> Result<Tile, String> {
+        match c {
+            '.' | 'S' | 'E' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(format!("unknown tile {c}")),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("#.#\n..#\n", tile).unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], Tile::Wall);
+
+        let digits = Grid::parse("123\n456", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+        assert_eq!(digits[Point::new(1, 1)], 5);
+    }
+
+    #[test]
+    fn test_parse_markers() {
+        let input = "S.#\n#.E\n#.E";
+        let (grid, markers) = Grid::parse_with_markers(input, &['S', 'E'], tile).unwrap();
+        assert_eq!(grid[Point::new(0, 0)], Tile::Open);
+        assert_eq!(markers[&'S'], vec![Point::new(0, 0)]);
+        assert_eq!(markers[&'E'], vec![Point::new(2, 1), Point::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Grid::parse("#..\n#.\n", tile),
+            Err(GridParseError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        let error = Grid::parse("#..\n.x.", tile).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid cell 'x' at line 2, column 2: unknown tile x"
+        );
+        assert_eq!(Grid::parse("", tile), Err(GridParseError::Empty));
+    }
+}
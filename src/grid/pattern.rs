@@ -0,0 +1,214 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use super::{Grid, Orientation, Point};
+
+const BASE: u64 = 0x100_0000_01b3;
+
+/// Polynomial prefix hashes of every row of a grid, so the hash of any run of cells within a
+/// row can be found in constant time.  Arithmetic wraps, so matches must still be verified.
+struct RowHashes {
+    width: usize,
+    prefix: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+fn cell_hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl RowHashes {
+    fn new<T: Clone + Default + PartialEq + Hash>(grid: &Grid<T>) -> Self {
+        let mut powers = vec![1u64; grid.width + 1];
+        for i in 1..powers.len() {
+            powers[i] = powers[i - 1].wrapping_mul(BASE);
+        }
+        let mut prefix = Vec::with_capacity((grid.width + 1) * grid.height);
+        for row in grid.data.chunks(grid.width.max(1)).take(grid.height) {
+            let mut hash = 0u64;
+            prefix.push(hash);
+            for value in row {
+                hash = hash.wrapping_mul(BASE).wrapping_add(cell_hash(value));
+                prefix.push(hash);
+            }
+        }
+        Self {
+            width: grid.width,
+            prefix,
+            powers,
+        }
+    }
+
+    /// The hash of the `len` cells of row `y` starting at column `x`.
+    fn run(&self, x: usize, y: usize, len: usize) -> u64 {
+        let row = y * (self.width + 1);
+        self.prefix[row + x + len].wrapping_sub(self.prefix[row + x].wrapping_mul(self.powers[len]))
+    }
+}
+
+/// A run of non-wildcard cells in one row of a pattern.
+struct Segment {
+    x: usize,
+    y: usize,
+    len: usize,
+    hash: u64,
+}
+
+fn segments<T: Clone + Default + PartialEq + Hash>(
+    pattern: &Grid<T>,
+    wildcard: Option<&T>,
+) -> Vec<Segment> {
+    let hashes = RowHashes::new(pattern);
+    let mut segments = Vec::new();
+    for y in 0..pattern.height {
+        let mut x = 0;
+        while x < pattern.width {
+            let is_wild = |x| Some(&pattern[Point::new(x, y)]) == wildcard;
+            if is_wild(x) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < pattern.width && !is_wild(x) {
+                x += 1;
+            }
+            let len = x - start;
+            segments.push(Segment {
+                x: start,
+                y,
+                len,
+                hash: hashes.run(start, y, len),
+            });
+        }
+    }
+    segments
+}
+
+impl<T: Clone + Default + PartialEq + Hash> Grid<T> {
+    /// The top left corner of every placement of `pattern` within the grid, in reading order.
+    /// Cells of the pattern equal to `wildcard` match anything.
+    ///
+    /// Each row of the pattern is split into runs of non-wildcard cells whose hashes are
+    /// compared against rolling hashes of the grid's rows, and only placements where every run
+    /// matches are compared cell by cell.
+    pub fn find_pattern(&self, pattern: &Grid<T>, wildcard: Option<&T>) -> Vec<Point> {
+        self.find_pattern_with(&RowHashes::new(self), pattern, wildcard)
+    }
+
+    /// Like `find_pattern`, but also searches for each of the 8 orientations of the pattern,
+    /// returning the top left corner of each transformed pattern that matches.  Orientations
+    /// that give the same pattern as an earlier one are skipped, so a symmetric pattern is not
+    /// reported more than once at the same place.
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &Grid<T>,
+        wildcard: Option<&T>,
+    ) -> Vec<(Point, Orientation)> {
+        let hashes = RowHashes::new(self);
+        let mut seen: Vec<Grid<T>> = Vec::new();
+        let mut matches = Vec::new();
+        for (orientation, oriented) in pattern.orientations() {
+            if seen.contains(&oriented) {
+                continue;
+            }
+            let found = self.find_pattern_with(&hashes, &oriented, wildcard);
+            matches.extend(found.into_iter().map(|p| (p, orientation)));
+            seen.push(oriented);
+        }
+        matches
+    }
+
+    fn find_pattern_with(
+        &self,
+        hashes: &RowHashes,
+        pattern: &Grid<T>,
+        wildcard: Option<&T>,
+    ) -> Vec<Point> {
+        if pattern.width > self.width || pattern.height > self.height {
+            return Vec::new();
+        }
+        let segments = segments(pattern, wildcard);
+        let mut matches = Vec::new();
+        for y in 0..=self.height - pattern.height {
+            for x in 0..=self.width - pattern.width {
+                let hashes_match = segments
+                    .iter()
+                    .all(|s| hashes.run(x + s.x, y + s.y, s.len) == s.hash);
+                if hashes_match && self.matches_at(Point::new(x, y), pattern, wildcard) {
+                    matches.push(Point::new(x, y));
+                }
+            }
+        }
+        matches
+    }
+
+    fn matches_at(&self, corner: Point, pattern: &Grid<T>, wildcard: Option<&T>) -> bool {
+        pattern.iter().all(|(p, value)| {
+            Some(value) == wildcard || *value == self[Point::new(corner.x + p.x, corner.y + p.y)]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORD_SEARCH: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_word_search() {
+        let grid = Grid::from(WORD_SEARCH);
+        let wild = Some(&b'.');
+        let straight = grid.find_pattern_oriented(&Grid::from("XMAS"), wild);
+        let diagonal = grid.find_pattern_oriented(&Grid::from("X...\n.M..\n..A.\n...S"), wild);
+        assert_eq!(straight.len() + diagonal.len(), 18);
+
+        let crosses = grid.find_pattern_oriented(&Grid::from("M.S\n.A.\nM.S"), wild);
+        assert_eq!(crosses.len(), 9);
+        assert_eq!(
+            grid.find_pattern(&Grid::from("M.S\n.A.\nM.S"), wild).len(),
+            2
+        );
+        assert!(crosses.contains(&(Point::new(1, 0), Orientation::Identity)));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut seed = 12345u64;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 61) as u8 % 3
+        };
+        let mut random_grid = |width, height| {
+            let rows = (0..height).map(|_| (0..width).map(|_| random()).collect());
+            Grid::from(rows.collect::<Vec<Vec<u8>>>())
+        };
+        let grid = random_grid(40, 30);
+        let mut pattern = random_grid(3, 2);
+        pattern[Point::new(1, 0)] = 9;
+        for wildcard in [None, Some(&9)] {
+            let expected: Vec<_> = grid
+                .iter()
+                .map(|(p, _)| p)
+                .filter(|p| p.x + 3 <= 40 && p.y + 2 <= 30)
+                .filter(|&p| grid.matches_at(p, &pattern, wildcard))
+                .collect();
+            assert_eq!(grid.find_pattern(&pattern, wildcard), expected);
+        }
+        assert!(!grid.find_pattern(&pattern, Some(&9)).is_empty());
+        assert!(grid.find_pattern(&Grid::new(41, 1, 0), None).is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use super::{Grid, Point, Vector};
+
+/// The integer points on a line segment, from one end to the other inclusive.  Horizontal,
+/// vertical and 45° segments step by a fixed unit vector; other slopes use Bresenham's
+/// algorithm, so consecutive points are always adjacent.
+#[derive(Debug, Clone)]
+pub struct LinePoints {
+    current: Vector,
+    step: Vector,
+    delta: Vector,
+    error: isize,
+    remaining: usize,
+    diagonal_or_straight: bool,
+}
+
+/// The points on the segment from `from` to `to`, which can be `Point`s, `Vector`s or
+/// `Position<isize>`s.
+pub fn line_points(from: impl Into<Vector>, to: impl Into<Vector>) -> LinePoints {
+    let (from, to) = (from.into(), to.into());
+    let delta = (to - from).abs();
+    let step = Vector::new((to.x - from.x).signum(), (to.y - from.y).signum());
+    LinePoints {
+        current: from,
+        step,
+        delta,
+        error: delta.x - delta.y,
+        remaining: delta.x.max(delta.y) as usize + 1,
+        diagonal_or_straight: delta.x == 0 || delta.y == 0 || delta.x == delta.y,
+    }
+}
+
+impl Iterator for LinePoints {
+    type Item = Vector;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let point = self.current;
+        if self.diagonal_or_straight {
+            self.current += self.step;
+        } else {
+            let doubled = 2 * self.error;
+            if doubled > -self.delta.y {
+                self.error -= self.delta.y;
+                self.current.x += self.step.x;
+            }
+            if doubled < self.delta.x {
+                self.error += self.delta.x;
+                self.current.y += self.step.y;
+            }
+        }
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for LinePoints {}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    fn line_in_bounds(&self, from: Point, to: Point) -> impl Iterator<Item = Point> + use<T> {
+        let (width, height) = (self.width, self.height);
+        line_points(from, to)
+            .filter(move |p| p.x < width as isize && p.y < height as isize)
+            .map(Point::from)
+    }
+
+    /// Sets every cell on the segment from `from` to `to`, ignoring any outside the grid.
+    pub fn draw_line(&mut self, from: Point, to: Point, value: T) {
+        for p in self.line_in_bounds(from, to) {
+            self[p] = value.clone();
+        }
+    }
+
+    /// Draws a segment between each consecutive pair of points, e.g. a rock path.
+    pub fn draw_polyline(&mut self, points: impl IntoIterator<Item = Point>, value: T) {
+        let mut points = points.into_iter();
+        let Some(mut previous) = points.next() else {
+            return;
+        };
+        self.draw_line(previous, previous, value.clone());
+        for p in points {
+            self.draw_line(previous, p, value.clone());
+            previous = p;
+        }
+    }
+}
+
+impl Grid<usize> {
+    /// Adds one to every cell on the segment, so each cell counts the lines through it.
+    pub fn add_line(&mut self, from: Point, to: Point) {
+        for p in self.line_in_bounds(from, to) {
+            self[p] += 1;
+        }
+    }
+
+    /// The number of cells covered by at least two lines.
+    pub fn count_overlaps(&self) -> usize {
+        self.data.iter().filter(|&&count| count >= 2).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos::Position;
+
+    const VENTS: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    fn path(line: &str) -> Vec<Point> {
+        line.split(" -> ")
+            .map(|pair| {
+                let (x, y) = pair.split_once(',').unwrap();
+                Point::new(x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_line_points() {
+        let diagonal: Vec<_> = line_points(Point::new(3, 3), Point::new(0, 0)).collect();
+        assert_eq!(diagonal.len(), 4);
+        assert_eq!(diagonal[1], Vector::new(2, 2));
+        assert_eq!(line_points(Vector::new(1, 1), Vector::new(1, 1)).count(), 1);
+
+        let from = Position::<isize>::new(-2, 5);
+        for to in [Vector::new(5, 1), Vector::new(-4, -3), Vector::new(0, 12)] {
+            let points: Vec<_> = line_points(from, to).collect();
+            let delta = (to - from.into()).abs();
+            assert_eq!(points.len() as isize, delta.x.max(delta.y) + 1);
+            assert_eq!(points[0], from.into());
+            assert_eq!(*points.last().unwrap(), to);
+            for pair in points.windows(2) {
+                let step = (pair[1] - pair[0]).abs();
+                assert!(step.x <= 1 && step.y <= 1);
+            }
+        }
+        assert_eq!(
+            line_points(Vector::new(0, 0), Vector::new(4, 2)).collect::<Vec<_>>(),
+            [
+                Vector::new(0, 0),
+                Vector::new(1, 0),
+                Vector::new(2, 1),
+                Vector::new(3, 1),
+                Vector::new(4, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_overlaps() {
+        let mut straight = Grid::new(10, 10, 0usize);
+        let mut all = Grid::new(10, 10, 0usize);
+        for line in VENTS.lines() {
+            let ends = path(line);
+            let (from, to) = (ends[0], ends[1]);
+            if from.x == to.x || from.y == to.y {
+                straight.add_line(from, to);
+            }
+            all.add_line(from, to);
+        }
+        assert_eq!(straight.count_overlaps(), 5);
+        assert_eq!(all.count_overlaps(), 12);
+    }
+
+    #[test]
+    fn test_draw_polyline() {
+        let mut cave = Grid::new(10, 10, '.');
+        for line in [
+            "498,4 -> 498,6 -> 496,6",
+            "503,4 -> 502,4 -> 502,9 -> 494,9",
+        ] {
+            let points = path(line).into_iter().map(|p| Point::new(p.x - 494, p.y));
+            cave.draw_polyline(points, '#');
+        }
+        assert_eq!(cave.iter().filter(|(_, c)| **c == '#').count(), 20);
+        assert_eq!(
+            cave.render(|&c| c).to_string(),
+            "\
+..........
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.
+"
+        );
+
+        let mut single = Grid::new(3, 3, false);
+        single.draw_polyline([Point::new(1, 1)], true);
+        single.draw_line(Point::new(2, 0), Point::new(9, 7), true);
+        assert_eq!(single.iter().filter(|(_, b)| **b).count(), 2);
+    }
+}
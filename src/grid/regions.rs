@@ -0,0 +1,215 @@
+use super::{BoundingBox, Connectivity, Direction, Grid, Point, Vector};
+
+/// A summary of one connected region of equal cells, as produced by `Grid::label_regions`.
+/// `first` is the region's first cell in reading order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region {
+    pub id: usize,
+    pub first: Point,
+    pub area: usize,
+    pub bounding_box: BoundingBox,
+    pub perimeter: usize,
+    pub sides: usize,
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// The orthogonally connected points reachable from `start` through cells matching
+    /// `predicate`, including `start` itself.  Empty if `start` does not match.
+    pub fn flood_fill<F>(&self, start: Point, predicate: F) -> Vec<Point>
+    where
+        F: FnMut(Point, &T) -> bool,
+    {
+        self.flood_fill_with(start, Connectivity::Orthogonal, predicate)
+    }
+
+    pub fn flood_fill_with<F>(
+        &self,
+        start: Point,
+        connectivity: Connectivity,
+        mut predicate: F,
+    ) -> Vec<Point>
+    where
+        F: FnMut(Point, &T) -> bool,
+    {
+        if !predicate(start, &self[start]) {
+            return Vec::new();
+        }
+        let mut visited = Grid::new(self.width, self.height, false);
+        visited[start] = true;
+        let mut filled = vec![start];
+        let mut next = 0;
+        while next < filled.len() {
+            let current = filled[next];
+            next += 1;
+            for (p, value) in self.neighbors(current, connectivity) {
+                if !visited[p] && predicate(p, value) {
+                    visited[p] = true;
+                    filled.push(p);
+                }
+            }
+        }
+        filled
+    }
+
+    /// Labels every connected region of equal cells.  Region ids index the returned `Vec` and
+    /// are assigned in reading order of each region's first cell.
+    ///
+    /// Perimeter and sides are measured along cell edges whatever the connectivity, so two
+    /// diagonally touching cells of one region have separate fences.
+    pub fn label_regions(&self, connectivity: Connectivity) -> (Grid<usize>, Vec<Region>) {
+        let mut labels = Grid::new(self.width, self.height, usize::MAX);
+        let mut regions = Vec::new();
+        for (point, value) in self {
+            if labels[point] != usize::MAX {
+                continue;
+            }
+            // Fill inline, using `labels` as the visited grid, so each cell is only visited
+            // once however many regions there are.
+            let id = regions.len();
+            labels[point] = id;
+            let mut cells = vec![point];
+            let mut next = 0;
+            while next < cells.len() {
+                let current = cells[next];
+                next += 1;
+                for (p, v) in self.neighbors(current, connectivity) {
+                    if labels[p] == usize::MAX && v == value {
+                        labels[p] = id;
+                        cells.push(p);
+                    }
+                }
+            }
+            regions.push(Region {
+                id,
+                first: point,
+                area: cells.len(),
+                bounding_box: BoundingBox::new(point),
+                perimeter: 0,
+                sides: 0,
+            });
+            let region = regions.last_mut().unwrap();
+            for &cell in &cells {
+                region.bounding_box.include(cell);
+                region.perimeter += labels.edges(cell);
+                region.sides += labels.corners(cell);
+            }
+        }
+        (labels, regions)
+    }
+}
+
+impl Grid<usize> {
+    fn same_label(&self, point: Point, offset: Vector) -> bool {
+        self.add_vector(point, offset)
+            .is_some_and(|p| self[p] == self[point])
+    }
+
+    fn edges(&self, point: Point) -> usize {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .filter(|&d| !self.same_label(point, Vector::from_direction(d)))
+        .count()
+    }
+
+    // The number of sides of a region equals its number of corners.  A cell contributes an
+    // outer corner where both edges of a quadrant are fenced, and an inner corner where neither
+    // is fenced but the diagonal cell belongs to another region.
+    fn corners(&self, point: Point) -> usize {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .filter(|&d| {
+            let a = Vector::from_direction(d);
+            let b = Vector::from_direction(d.right());
+            match (self.same_label(point, a), self.same_label(point, b)) {
+                (false, false) => true,
+                (true, true) => !self.same_label(point, a + b),
+                _ => false,
+            }
+        })
+        .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARDEN: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = Grid::from("..#\n.##\n#..");
+        let filled = grid.flood_fill(Point::new(0, 0), |_, &c| c == b'.');
+        assert_eq!(filled.len(), 3);
+        let open = |_, &c: &u8| c == b'.';
+        let diagonal = grid.flood_fill_with(Point::new(0, 0), Connectivity::All, open);
+        assert_eq!(diagonal.len(), 5);
+        let blocked = grid.flood_fill(Point::new(2, 0), open);
+        assert!(blocked.is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let grid = Grid::from(GARDEN);
+        let (labels, regions) = grid.label_regions(Connectivity::Orthogonal);
+        assert_eq!(regions.len(), 11);
+        let price: usize = regions.iter().map(|r| r.area * r.perimeter).sum();
+        assert_eq!(price, 1930);
+        let discount: usize = regions.iter().map(|r| r.area * r.sides).sum();
+        assert_eq!(discount, 1206);
+
+        let r = &regions[0];
+        assert_eq!(grid[r.first], b'R');
+        assert_eq!(r.area, 12);
+        assert_eq!(r.bounding_box.min, Point::new(0, 0));
+        assert_eq!(r.bounding_box.max, Point::new(4, 3));
+        assert_eq!(labels[Point::new(2, 3)], r.id);
+    }
+
+    #[test]
+    fn test_sides_with_holes() {
+        let grid = Grid::from("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let (_, regions) = grid.label_regions(Connectivity::Orthogonal);
+        let discount: usize = regions.iter().map(|r| r.area * r.sides).sum();
+        assert_eq!(discount, 368);
+
+        let (_, regions) = grid.label_regions(Connectivity::All);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].area, 8);
+        assert_eq!(regions[1].perimeter, 16);
+    }
+
+    #[test]
+    fn test_many_regions() {
+        let mut board = Grid::new(400, 400, false);
+        for y in 0..400 {
+            for x in (y % 2..400).step_by(2) {
+                board[Point::new(x, y)] = true;
+            }
+        }
+        let (labels, regions) = board.label_regions(Connectivity::Orthogonal);
+        assert_eq!(regions.len(), 400 * 400);
+        assert_eq!(labels[Point::new(399, 399)], 400 * 400 - 1);
+        assert!(regions.iter().all(|r| r.area == 1 && r.sides == 4));
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{Direction, Grid, Point};
+
+/// Renders a grid as text through a cell to character closure, with optional overlays and
+/// axis labels.  Created by `Grid::render`; the text is produced by its `Display` impl.
+pub struct GridRenderer<'a, T: Clone + Default + PartialEq, F: Fn(&T) -> char> {
+    grid: &'a Grid<T>,
+    cell: F,
+    overlays: HashMap<Point, char>,
+    axis_labels: bool,
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    pub fn render<F: Fn(&T) -> char>(&'_ self, cell: F) -> GridRenderer<'_, T, F> {
+        GridRenderer {
+            grid: self,
+            cell,
+            overlays: HashMap::new(),
+            axis_labels: false,
+        }
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq, F: Fn(&T) -> char> GridRenderer<'a, T, F> {
+    /// Draws `c` over each of `points`, replacing earlier overlays.
+    pub fn overlay(mut self, points: impl IntoIterator<Item = Point>, c: char) -> Self {
+        self.overlays.extend(points.into_iter().map(|p| (p, c)));
+        self
+    }
+
+    /// Draws each step of `path` as the arrow of the direction it moves in.  The last point
+    /// shows the direction it was entered from; steps between non-adjacent points are drawn
+    /// as `*`.
+    pub fn overlay_path(mut self, path: &[Point]) -> Self {
+        for (i, pair) in path.windows(2).enumerate() {
+            let c = Direction::try_from_vector(pair[1] - pair[0]).map_or('*', char::from);
+            self.overlays.insert(pair[0], c);
+            if i + 2 == path.len() {
+                self.overlays.insert(pair[1], c);
+            }
+        }
+        self
+    }
+
+    /// Adds the x coordinate above each column, written vertically, and the y coordinate before
+    /// each row.
+    pub fn with_axis_labels(mut self) -> Self {
+        self.axis_labels = true;
+        self
+    }
+}
+
+impl<T: Clone + Default + PartialEq, F: Fn(&T) -> char> Display for GridRenderer<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn digits(n: usize) -> usize {
+            n.checked_ilog10().unwrap_or(0) as usize + 1
+        }
+        let row_label_width = digits(self.grid.height.saturating_sub(1));
+        if self.axis_labels {
+            let column_digits = digits(self.grid.width.saturating_sub(1)) as u32;
+            for place in (0..column_digits).rev() {
+                write!(f, "{:row_label_width$} ", "")?;
+                for x in 0..self.grid.width {
+                    write!(f, "{}", x / 10usize.pow(place) % 10)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        for y in 0..self.grid.height {
+            if self.axis_labels {
+                write!(f, "{y:>row_label_width$} ")?;
+            }
+            for x in 0..self.grid.width {
+                let point = Point::new(x, y);
+                let c = match self.overlays.get(&point) {
+                    Some(&c) => c,
+                    None => (self.cell)(&self.grid[point]),
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from(vec![vec![0, 1, 2], vec![3, 0, 5]]);
+        let text = grid.render(|&n| if n == 0 { '.' } else { '#' }).to_string();
+        assert_eq!(text, ".##\n#.#\n");
+
+        let digits = grid.render(|&n| char::from_digit(n, 10).unwrap());
+        assert_eq!(digits.to_string(), "012\n305\n");
+    }
+
+    #[test]
+    fn test_overlays() {
+        let grid = Grid::new(4, 3, false);
+        let path = [
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(0, 2),
+        ];
+        let text = grid
+            .render(|&wall| if wall { '#' } else { '.' })
+            .overlay([Point::new(3, 2)], 'E')
+            .overlay_path(&path)
+            .to_string();
+        assert_eq!(text, ">v..\nv<..\nv..E\n");
+    }
+
+    #[test]
+    fn test_axis_labels() {
+        let grid = Grid::new(12, 2, b'.');
+        let text = grid.render(|&c| c as char).with_axis_labels().to_string();
+        assert_eq!(
+            text,
+            "  000000000011\n  012345678901\n0 ............\n1 ............\n"
+        );
+    }
+}
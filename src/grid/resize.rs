@@ -0,0 +1,216 @@
+use super::{BoundingBox, Grid, Point};
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// A copy of the grid with a border `n` cells wide of `value` on every side.
+    pub fn pad(&self, n: usize, value: T) -> Self {
+        let mut result = Self::new(self.width + 2 * n, self.height + 2 * n, value);
+        for (p, cell) in self {
+            result[Point::new(p.x + n, p.y + n)] = cell.clone();
+        }
+        result
+    }
+
+    /// The cells inside `bounds`, which must lie within the grid.
+    pub fn crop(&self, bounds: BoundingBox) -> Self {
+        assert!(
+            self.in_bounds(bounds.max),
+            "crop bounds {bounds:?} outside {}x{} grid",
+            self.width,
+            self.height
+        );
+        let mut result = Self::empty_with_capacity(bounds.width(), bounds.height());
+        for y in bounds.min.y..=bounds.max.y {
+            let start = y * self.width;
+            result
+                .data
+                .extend_from_slice(&self.data[start + bounds.min.x..=start + bounds.max.x]);
+        }
+        result
+    }
+
+    /// The smallest box holding every cell that does not match `predicate`, or `None` if every
+    /// cell matches.
+    pub fn trim_bounds<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<BoundingBox> {
+        let mut bounds: Option<BoundingBox> = None;
+        for (p, cell) in self {
+            if !predicate(cell) {
+                match &mut bounds {
+                    Some(b) => b.include(p),
+                    None => bounds = Some(BoundingBox::new(p)),
+                }
+            }
+        }
+        bounds
+    }
+
+    /// A copy with the border rows and columns whose cells all match `predicate` removed.  If
+    /// every cell matches the result is empty.
+    pub fn trim<F: FnMut(&T) -> bool>(&self, predicate: F) -> Self {
+        match self.trim_bounds(predicate) {
+            Some(bounds) => self.crop(bounds),
+            None => Self::new_default(0, 0),
+        }
+    }
+
+    /// The grid repeated `nx` times across and `ny` times down.
+    pub fn tile(&self, nx: usize, ny: usize) -> Self {
+        let mut result = Self::empty_with_capacity(self.width * nx, self.height * ny);
+        for _ in 0..ny {
+            for row in self.data.chunks(self.width.max(1)).take(self.height) {
+                for _ in 0..nx {
+                    result.data.extend_from_slice(row);
+                }
+            }
+        }
+        result
+    }
+
+    /// Inserts a row of `value` before row `y`, so that it becomes row `y`.
+    pub fn insert_row(&mut self, y: usize, value: T) {
+        assert!(y <= self.height, "row {y} out of range");
+        let start = y * self.width;
+        self.data
+            .splice(start..start, std::iter::repeat_n(value, self.width));
+        self.height += 1;
+    }
+
+    /// Removes row `y` and returns its cells.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.height, "row {y} out of range");
+        let start = y * self.width;
+        self.height -= 1;
+        self.data.drain(start..start + self.width).collect()
+    }
+
+    /// Inserts a column of `value` before column `x`, so that it becomes column `x`.
+    pub fn insert_column(&mut self, x: usize, value: T) {
+        assert!(x <= self.width, "column {x} out of range");
+        if self.width == 0 {
+            self.data = vec![value; self.height];
+            self.width = 1;
+            return;
+        }
+        let mut data = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.data.chunks(self.width) {
+            data.extend_from_slice(&row[..x]);
+            data.push(value.clone());
+            data.extend_from_slice(&row[x..]);
+        }
+        self.data = data;
+        self.width += 1;
+    }
+
+    /// Removes column `x` and returns its cells.
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        assert!(x < self.width, "column {x} out of range");
+        let mut removed = Vec::with_capacity(self.height);
+        let mut i = 0;
+        let width = self.width;
+        self.data.retain(|cell| {
+            let keep = i % width != x;
+            if !keep {
+                removed.push(cell.clone());
+            }
+            i += 1;
+            keep
+        });
+        self.width -= 1;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIVERSE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_cosmic_expansion() {
+        let mut universe = Grid::from(UNIVERSE);
+        for y in (0..universe.height).rev() {
+            if universe.row(y).all(|(_, &c)| c == b'.') {
+                universe.insert_row(y, b'.');
+            }
+        }
+        for x in (0..universe.width).rev() {
+            if universe.column(x).all(|(_, &c)| c == b'.') {
+                universe.insert_column(x, b'.');
+            }
+        }
+        assert_eq!((universe.width, universe.height), (13, 12));
+        let galaxies: Vec<Point> = universe
+            .iter()
+            .filter(|(_, c)| **c == b'#')
+            .map(|(p, _)| p)
+            .collect();
+        let total: usize = galaxies
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| galaxies[i + 1..].iter().map(move |b| a.manhattan(b)))
+            .sum();
+        assert_eq!(total, 374);
+
+        assert_eq!(universe.remove_row(3), vec![b'.'; 13]);
+        assert_eq!(universe.remove_column(0)[1], b'.');
+        assert_eq!((universe.width, universe.height), (12, 11));
+    }
+
+    #[test]
+    fn test_pad_crop_trim() {
+        let grid = Grid::from("ab\ncd");
+        let padded = grid.pad(2, b'.');
+        assert_eq!((padded.width, padded.height), (6, 6));
+        assert_eq!(padded[Point::new(3, 3)], b'd');
+        assert_eq!(padded.trim(|&c| c == b'.'), grid);
+        assert_eq!(
+            padded.trim_bounds(|&c| c == b'.'),
+            Some(BoundingBox {
+                min: Point::new(2, 2),
+                max: Point::new(3, 3)
+            })
+        );
+        let mut bounds = BoundingBox::new(Point::new(1, 0));
+        bounds.include(Point::new(2, 2));
+        assert_eq!(
+            Grid::from("abc\ndef\nghi").crop(bounds),
+            Grid::from("bc\nef\nhi")
+        );
+        assert_eq!(grid.trim(|_| true).width, 0);
+    }
+
+    #[test]
+    fn test_tile_and_edits() {
+        let grid = Grid::from("ab\ncd");
+        assert_eq!(
+            grid.tile(3, 2),
+            Grid::from("ababab\ncdcdcd\nababab\ncdcdcd")
+        );
+
+        let mut edited = grid.clone();
+        edited.insert_column(1, b'-');
+        edited.insert_row(2, b'=');
+        edited.insert_column(3, b'|');
+        assert_eq!(edited, Grid::from("a-b|\nc-d|\n===|"));
+        assert_eq!(edited.remove_column(1), b"--=");
+        assert_eq!(edited.remove_row(0), b"ab|");
+        assert_eq!(edited, Grid::from("cd|\n==|"));
+
+        let mut column = Grid::from("a\nb");
+        column.remove_column(0);
+        assert_eq!((column.width, column.height), (0, 2));
+        column.insert_column(0, b'x');
+        assert_eq!(column, Grid::from("x\nx"));
+        assert_eq!(column[Point::new(0, 1)], b'x');
+    }
+}
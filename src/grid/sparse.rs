@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use super::{Connectivity, Grid, Point, Vector};
+
+/// An unbounded grid indexed by signed coordinates, storing only the cells that have been set.
+/// Unset cells read as `default`.  The bounds grow to cover every cell that has been set, and
+/// are not shrunk by `remove`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T: Clone + Default + PartialEq> {
+    cells: HashMap<Vector, T>,
+    default: T,
+    bounds: Option<(Vector, Vector)>,
+}
+
+impl<T: Clone + Default + PartialEq> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new_default()
+    }
+}
+
+impl<T: Clone + Default + PartialEq> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn new_default() -> Self {
+        Self::new(T::default())
+    }
+
+    fn include(&mut self, point: Vector) {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Vector::new(min.x.min(point.x), min.y.min(point.y)),
+                Vector::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        });
+    }
+
+    pub fn get(&self, point: impl Into<Vector>) -> &T {
+        self.cells.get(&point.into()).unwrap_or(&self.default)
+    }
+
+    /// Mutable access to a cell, setting it to the default first if it is unset.
+    pub fn get_mut(&mut self, point: impl Into<Vector>) -> &mut T {
+        let point = point.into();
+        self.include(point);
+        self.cells
+            .entry(point)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    pub fn set(&mut self, point: impl Into<Vector>, value: T) {
+        let point = point.into();
+        self.include(point);
+        self.cells.insert(point, value);
+    }
+
+    pub fn remove(&mut self, point: impl Into<Vector>) -> Option<T> {
+        self.cells.remove(&point.into())
+    }
+
+    pub fn contains(&self, point: impl Into<Vector>) -> bool {
+        self.cells.contains_key(&point.into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// The inclusive `(min, max)` corners of every cell that has been set.
+    pub fn bounds(&self) -> Option<(Vector, Vector)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// The cells that have been set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    pub fn orthogonal_neighbors(&'_ self, center: impl Into<Vector>) -> SparseNeighbors<'_, T> {
+        self.neighbors(center, Connectivity::Orthogonal)
+    }
+
+    pub fn all_neighbors(&'_ self, center: impl Into<Vector>) -> SparseNeighbors<'_, T> {
+        self.neighbors(center, Connectivity::All)
+    }
+
+    pub fn neighbors(
+        &'_ self,
+        center: impl Into<Vector>,
+        connectivity: Connectivity,
+    ) -> SparseNeighbors<'_, T> {
+        SparseNeighbors {
+            grid: self,
+            center: center.into(),
+            offsets: connectivity.offsets(),
+            current_direction: 0,
+        }
+    }
+
+    /// A dense copy of the grid over its bounds, together with the coordinates of the dense
+    /// grid's top left cell.
+    pub fn to_grid(&self) -> (Grid<T>, Vector) {
+        let Some((min, _)) = self.bounds else {
+            return (Grid::empty_with_capacity(0, 0), Vector::default());
+        };
+        let mut grid = Grid::new(self.width(), self.height(), self.default.clone());
+        for (point, value) in self.iter() {
+            let offset = point - min;
+            grid[Point::new(offset.x as usize, offset.y as usize)] = value.clone();
+        }
+        (grid, min)
+    }
+
+    /// One line of text per row of the bounds, mapping each cell to a character.
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+        let mut result = String::with_capacity((self.width() + 1) * self.height());
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                result.push(cell(self.get(Vector::new(x, y))));
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<T: Clone + Default + PartialEq> FromIterator<(Vector, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vector, T)>>(iter: I) -> Self {
+        let mut grid = Self::new_default();
+        for (point, value) in iter {
+            grid.set(point, value);
+        }
+        grid
+    }
+}
+
+/// Keeps only the cells of `grid` that differ from the default.
+impl<T: Clone + Default + PartialEq> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        grid.iter()
+            .filter(|(_, v)| **v != T::default())
+            .map(|(p, v)| (Vector::from(p), v.clone()))
+            .collect()
+    }
+}
+
+pub struct SparseNeighbors<'a, T: Clone + Default + PartialEq> {
+    grid: &'a SparseGrid<T>,
+    center: Vector,
+    offsets: &'static [Vector],
+    current_direction: usize,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for SparseNeighbors<'a, T> {
+    type Item = (Vector, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offsets.get(self.current_direction)?;
+        self.current_direction += 1;
+        let p = self.center + *offset;
+        Some((p, self.grid.get(p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos::Position;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new('.');
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        grid.set(Vector::new(-2, 1), '#');
+        grid.set(Position::<isize>::new(1, -1), '@');
+        assert_eq!(*grid.get(Vector::new(0, 0)), '.');
+        assert_eq!(*grid.get(Vector::new(-2, 1)), '#');
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new(-2, -1), Vector::new(1, 1)))
+        );
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.render(|&c| c), "...@\n....\n#...\n");
+
+        *grid.get_mut(Vector::new(5, 0)) = '$';
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.width(), 8);
+    }
+
+    #[test]
+    fn test_sparse_neighbors() {
+        let grid: SparseGrid<u8> = [(Vector::new(0, -1), 1), (Vector::new(1, 1), 2)]
+            .into_iter()
+            .collect();
+        let ortho: Vec<_> = grid.orthogonal_neighbors(Vector::new(0, 0)).collect();
+        assert_eq!(ortho.len(), 4);
+        assert_eq!(ortho[0], (Vector::new(0, -1), &1));
+        let total: u8 = grid.all_neighbors(Vector::new(0, 0)).map(|(_, v)| v).sum();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_sparse_to_grid() {
+        let dense = Grid::from("#..\n..#");
+        let mut sparse: SparseGrid<u8> = SparseGrid::new(b'.');
+        sparse.set(Vector::new(-1, -1), b'#');
+        sparse.set(Vector::new(1, 0), b'#');
+        let (grid, origin) = sparse.to_grid();
+        assert_eq!(origin, Vector::new(-1, -1));
+        assert_eq!(grid, dense);
+
+        let counts = Grid::from(vec![vec![0, 3, 0], vec![1, 0, 0]]);
+        let sparse = SparseGrid::from(&counts);
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(*sparse.get(Vector::new(1, 0)), 3);
+    }
+}
@@ -0,0 +1,169 @@
+use super::{Grid, Point};
+
+/// One of the 8 symmetries of a rectangle.  Rotations are clockwise, `FlipHorizontal` mirrors
+/// left to right, `Transpose` mirrors in the main diagonal and `AntiTranspose` in the other one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Orientation {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+        Orientation::Transpose,
+        Orientation::AntiTranspose,
+    ];
+
+    pub fn inverse(self) -> Self {
+        match self {
+            Orientation::Rotate90 => Orientation::Rotate270,
+            Orientation::Rotate270 => Orientation::Rotate90,
+            other => other,
+        }
+    }
+
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90
+                | Orientation::Rotate270
+                | Orientation::Transpose
+                | Orientation::AntiTranspose
+        )
+    }
+
+    /// The `(width, height)` of a `width` by `height` grid after this transform.
+    pub fn dimensions(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Where `point` of a `width` by `height` grid ends up after this transform.
+    pub fn map_point(self, point: Point, width: usize, height: usize) -> Point {
+        let Point { x, y } = point;
+        match self {
+            Orientation::Identity => Point::new(x, y),
+            Orientation::Rotate90 => Point::new(height - 1 - y, x),
+            Orientation::Rotate180 => Point::new(width - 1 - x, height - 1 - y),
+            Orientation::Rotate270 => Point::new(y, width - 1 - x),
+            Orientation::FlipHorizontal => Point::new(width - 1 - x, y),
+            Orientation::FlipVertical => Point::new(x, height - 1 - y),
+            Orientation::Transpose => Point::new(y, x),
+            Orientation::AntiTranspose => Point::new(height - 1 - y, width - 1 - x),
+        }
+    }
+
+    /// The point of the original `width` by `height` grid that ends up at `point` of the
+    /// transformed grid.
+    pub fn unmap_point(self, point: Point, width: usize, height: usize) -> Point {
+        let (width, height) = self.dimensions(width, height);
+        self.inverse().map_point(point, width, height)
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    pub fn transformed(&self, orientation: Orientation) -> Self {
+        let (width, height) = orientation.dimensions(self.width, self.height);
+        let mut result = Self::empty_with_capacity(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let source = orientation.unmap_point(Point::new(x, y), self.width, self.height);
+                result.data.push(self[source].clone());
+            }
+        }
+        result
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.transformed(Orientation::Rotate90)
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.transformed(Orientation::Rotate180)
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self {
+        self.transformed(Orientation::Rotate270)
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transformed(Orientation::Transpose)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.transformed(Orientation::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.transformed(Orientation::FlipVertical)
+    }
+
+    /// Every orientation of the grid, starting with the grid itself.
+    pub fn orientations(&self) -> impl Iterator<Item = (Orientation, Self)> + '_ {
+        Orientation::ALL
+            .into_iter()
+            .map(|orientation| (orientation, self.transformed(orientation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotations() {
+        let grid = Grid::from("abc\ndef");
+        assert_eq!(grid.rotate_clockwise(), Grid::from("da\neb\nfc"));
+        assert_eq!(grid.rotate_180(), Grid::from("fed\ncba"));
+        assert_eq!(grid.rotate_anticlockwise(), Grid::from("cf\nbe\nad"));
+        assert_eq!(grid.transpose(), Grid::from("ad\nbe\ncf"));
+        assert_eq!(grid.flip_horizontal(), Grid::from("cba\nfed"));
+        assert_eq!(grid.flip_vertical(), Grid::from("def\nabc"));
+        assert_eq!(
+            grid.transformed(Orientation::AntiTranspose),
+            Grid::from("fc\neb\nda")
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+    }
+
+    #[test]
+    fn test_point_mapping() {
+        let grid = Grid::from("abc\ndef");
+        for (orientation, transformed) in grid.orientations() {
+            for (point, value) in &grid {
+                let mapped = orientation.map_point(point, grid.width, grid.height);
+                assert_eq!(transformed[mapped], *value);
+                assert_eq!(
+                    orientation.unmap_point(mapped, grid.width, grid.height),
+                    point
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_orientations_distinct() {
+        let grid = Grid::from("ab\ncd");
+        let all: Vec<_> = grid.orientations().map(|(_, g)| g).collect();
+        assert_eq!(all.len(), 8);
+        for (i, a) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|b| a != b));
+        }
+    }
+}
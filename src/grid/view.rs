@@ -0,0 +1,323 @@
+use std::ops::{Index, IndexMut};
+
+use super::{Connectivity, Grid, Point, Vector};
+
+/// A borrowed rectangular part of a `Grid`.  Points are relative to the view's top left corner.
+#[derive(Debug)]
+pub struct GridView<'a, T: Clone + Default + PartialEq> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A mutably borrowed rectangular part of a `Grid`.  Points are relative to the view's top
+/// left corner.
+#[derive(Debug)]
+pub struct GridViewMut<'a, T: Clone + Default + PartialEq> {
+    grid: &'a mut Grid<T>,
+    origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    fn check_view(&self, top_left: Point, width: usize, height: usize) {
+        assert!(
+            top_left.x + width <= self.width && top_left.y + height <= self.height,
+            "view {width}x{height} at {top_left} does not fit in grid {}x{}",
+            self.width,
+            self.height
+        );
+    }
+
+    pub fn view(&'_ self, top_left: Point, width: usize, height: usize) -> GridView<'_, T> {
+        self.check_view(top_left, width, height);
+        GridView {
+            grid: self,
+            origin: top_left,
+            width,
+            height,
+        }
+    }
+
+    pub fn view_mut(
+        &'_ mut self,
+        top_left: Point,
+        width: usize,
+        height: usize,
+    ) -> GridViewMut<'_, T> {
+        self.check_view(top_left, width, height);
+        GridViewMut {
+            grid: self,
+            origin: top_left,
+            width,
+            height,
+        }
+    }
+
+    /// Every `width` by `height` view of the grid, in reading order of their top left corners.
+    pub fn windows(&'_ self, width: usize, height: usize) -> Windows<'_, T> {
+        Windows {
+            grid: self,
+            width,
+            height,
+            next: (width <= self.width && height <= self.height).then_some(Point::new(0, 0)),
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Copy for GridView<'_, T> {}
+
+impl<'a, T: Clone + Default + PartialEq> GridView<'a, T> {
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn in_bounds(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    /// The point of the underlying grid that `point` of this view refers to.
+    pub fn to_grid_point(&self, point: Point) -> Point {
+        Point::new(self.origin.x + point.x, self.origin.y + point.y)
+    }
+
+    pub fn get(&self, point: Point) -> &'a T {
+        assert!(self.in_bounds(point), "{point} is outside the view");
+        self.grid.get(self.to_grid_point(point))
+    }
+
+    pub fn add_vector(&self, point: Point, vector: Vector) -> Option<Point> {
+        (point + vector).filter(|p| self.in_bounds(*p))
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.height, "row {y} is outside the view");
+        let start = self.to_grid_point(Point::new(0, y));
+        &self.grid[start.y][start.x..start.x + self.width]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + use<'a, T> {
+        let view = *self;
+        (0..self.height).flat_map(move |y| {
+            view.row(y)
+                .iter()
+                .enumerate()
+                .map(move |(x, value)| (Point::new(x, y), value))
+        })
+    }
+
+    pub fn orthogonal_neighbors(&self, center: Point) -> ViewNeighbors<'a, T> {
+        self.neighbors(center, Connectivity::Orthogonal)
+    }
+
+    pub fn all_neighbors(&self, center: Point) -> ViewNeighbors<'a, T> {
+        self.neighbors(center, Connectivity::All)
+    }
+
+    pub fn neighbors(&self, center: Point, connectivity: Connectivity) -> ViewNeighbors<'a, T> {
+        ViewNeighbors {
+            view: *self,
+            center,
+            offsets: connectivity.offsets(),
+            current_direction: 0,
+        }
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        let mut grid = Grid::empty_with_capacity(self.width, self.height);
+        for y in 0..self.height {
+            grid.data.extend_from_slice(self.row(y));
+        }
+        grid
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Index<Point> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+impl<T: Clone + Default + PartialEq> PartialEq<Grid<T>> for GridView<'_, T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && (0..self.height).all(|y| self.row(y) == &other[y])
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> GridViewMut<'a, T> {
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn in_bounds(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn as_view(&'_ self) -> GridView<'_, T> {
+        GridView {
+            grid: self.grid,
+            origin: self.origin,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn get(&self, point: Point) -> &T {
+        assert!(self.in_bounds(point), "{point} is outside the view");
+        let point = Point::new(self.origin.x + point.x, self.origin.y + point.y);
+        self.grid.get(point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> &mut T {
+        assert!(self.in_bounds(point), "{point} is outside the view");
+        let point = Point::new(self.origin.x + point.x, self.origin.y + point.y);
+        self.grid.get_mut(point)
+    }
+
+    pub fn set(&mut self, point: Point, value: T) {
+        *self.get_mut(point) = value;
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} is outside the view");
+        let x = self.origin.x;
+        &mut self.grid[self.origin.y + y][x..x + self.width]
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for y in 0..self.height {
+            self.row_mut(y).fill(value.clone());
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Index<Point> for GridViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+impl<T: Clone + Default + PartialEq> IndexMut<Point> for GridViewMut<'_, T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+    }
+}
+
+pub struct ViewNeighbors<'a, T: Clone + Default + PartialEq> {
+    view: GridView<'a, T>,
+    center: Point,
+    offsets: &'static [Vector],
+    current_direction: usize,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for ViewNeighbors<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current_direction < self.offsets.len() {
+            let offset = self.offsets[self.current_direction];
+            self.current_direction += 1;
+
+            if let Some(p) = self.view.add_vector(self.center, offset) {
+                return Some((p, self.view.get(p)));
+            }
+        }
+        None
+    }
+}
+
+pub struct Windows<'a, T: Clone + Default + PartialEq> {
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    next: Option<Point>,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for Windows<'a, T> {
+    type Item = GridView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let top_left = self.next?;
+        self.next = if top_left.x + self.width < self.grid.width {
+            Some(Point::new(top_left.x + 1, top_left.y))
+        } else if top_left.y + self.height < self.grid.height {
+            Some(Point::new(0, top_left.y + 1))
+        } else {
+            None
+        };
+        Some(self.grid.view(top_left, self.width, self.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::from("abcd\nefgh\nijkl");
+        let view = grid.view(Point::new(1, 1), 2, 2);
+        assert_eq!(view[Point::new(0, 0)], b'f');
+        assert_eq!(view[Point::new(1, 1)], b'k');
+        assert_eq!(view.row(1), b"jk");
+        assert_eq!(view.to_grid_point(Point::new(1, 0)), Point::new(2, 1));
+        assert_eq!(view.iter().map(|(_, &c)| c).collect::<Vec<_>>(), b"fgjk");
+        assert_eq!(view.orthogonal_neighbors(Point::new(0, 0)).count(), 2);
+        assert_eq!(view.all_neighbors(Point::new(0, 0)).count(), 3);
+        assert_eq!(view, Grid::from("fg\njk"));
+        assert_eq!(view.to_grid(), Grid::from("fg\njk"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_view_out_of_bounds() {
+        let grid = Grid::from("abc\ndef");
+        grid.view(Point::new(2, 0), 2, 2);
+    }
+
+    #[test]
+    fn test_view_mut() {
+        let mut grid = Grid::from("....\n....\n....");
+        let mut view = grid.view_mut(Point::new(1, 1), 3, 2);
+        view.fill(b'#');
+        view[Point::new(0, 0)] = b'@';
+        assert_eq!(view.as_view().row(0), b"@##");
+        assert_eq!(grid, Grid::from("....\n.@##\n.###"));
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = Grid::from("abc\ndef\nghi");
+        let windows: Vec<_> = grid.windows(2, 2).map(|w| w.origin()).collect();
+        assert_eq!(
+            windows,
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(1, 1)
+            ]
+        );
+        assert_eq!(grid.windows(3, 3).count(), 1);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+        let x_count = grid
+            .windows(3, 1)
+            .filter(|w| w.iter().any(|(_, &c)| c == b'e'))
+            .count();
+        assert_eq!(x_count, 1);
+    }
+}
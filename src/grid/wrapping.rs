@@ -0,0 +1,117 @@
+use super::{Connectivity, Direction, Grid, Point, Vector};
+
+impl<T: Clone + Default + PartialEq> Grid<T> {
+    /// Adds `vector` to `point`, wrapping around the edges as if the grid were a torus.  The
+    /// vector may be any size, so a velocity can be multiplied by a number of steps first.
+    pub fn wrapping_add_vector(&self, point: Point, vector: Vector) -> Point {
+        fn wrap(value: usize, delta: isize, size: usize) -> usize {
+            let size = size as isize;
+            (value as isize + delta.rem_euclid(size)).rem_euclid(size) as usize
+        }
+        Point::new(
+            wrap(point.x, vector.x, self.width),
+            wrap(point.y, vector.y, self.height),
+        )
+    }
+
+    pub fn wrapping_add_direction(&self, point: Point, direction: Direction) -> Point {
+        self.wrapping_add_vector(point, Vector::from_direction(direction))
+    }
+
+    /// The four neighbours of `center` on a torus.  On grids narrower than three cells the same
+    /// neighbour can appear more than once.
+    pub fn wrapping_orthogonal_neighbors(&'_ self, center: Point) -> WrappingNeighbors<'_, T> {
+        self.wrapping_neighbors(center, Connectivity::Orthogonal)
+    }
+
+    /// The eight neighbours of `center` on a torus.  On grids narrower than three cells the same
+    /// neighbour can appear more than once.
+    pub fn wrapping_all_neighbors(&'_ self, center: Point) -> WrappingNeighbors<'_, T> {
+        self.wrapping_neighbors(center, Connectivity::All)
+    }
+
+    pub fn wrapping_neighbors(
+        &'_ self,
+        center: Point,
+        connectivity: Connectivity,
+    ) -> WrappingNeighbors<'_, T> {
+        WrappingNeighbors {
+            grid: self,
+            center,
+            offsets: connectivity.offsets(),
+            current_direction: 0,
+        }
+    }
+}
+
+pub struct WrappingNeighbors<'a, T: Clone + Default + PartialEq> {
+    grid: &'a Grid<T>,
+    center: Point,
+    offsets: &'static [Vector],
+    current_direction: usize,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for WrappingNeighbors<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offsets.get(self.current_direction)?;
+        self.current_direction += 1;
+        let p = self.grid.wrapping_add_vector(self.center, *offset);
+        Some((p, &self.grid[p]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping_add() {
+        let grid = Grid::new(11, 7, 0u8);
+        let start = Point::new(2, 4);
+        let velocity = Vector::new(2, -3);
+        let mut p = start;
+        for _ in 0..5 {
+            p = grid.wrapping_add_vector(p, velocity);
+        }
+        assert_eq!(p, Point::new(1, 3));
+        assert_eq!(grid.wrapping_add_vector(start, velocity * 5), p);
+        assert_eq!(
+            grid.wrapping_add_vector(start, velocity * 1_000_000_007),
+            grid.wrapping_add_vector(start, velocity * (1_000_000_007 % 77))
+        );
+        assert_eq!(
+            grid.wrapping_add_direction(Point::new(0, 0), Direction::West),
+            Point::new(10, 0)
+        );
+        assert_eq!(
+            grid.wrapping_add_direction(Point::new(0, 6), Direction::South),
+            Point::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let grid = Grid::new(4, 3, 0u8);
+        let corner = Point::new(0, 0);
+        let ortho: Vec<_> = grid
+            .wrapping_orthogonal_neighbors(corner)
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(
+            ortho,
+            vec![
+                Point::new(0, 2),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(3, 0)
+            ]
+        );
+        assert_eq!(grid.wrapping_all_neighbors(corner).count(), 8);
+        assert!(
+            grid.wrapping_all_neighbors(corner)
+                .any(|(p, _)| p == Point::new(3, 2))
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::ops::{Index, IndexMut};
+
+use crate::pos3d::{Neighbors3, Pos3};
+
+pub type Pos = Pos3<usize>;
+
+/// Which cubes count as neighbours: those sharing a face (6), a face or an edge (18), or any
+/// cube touching at all (26).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity3 {
+    #[default]
+    Faces,
+    FacesAndEdges,
+    All,
+}
+
+/// A dense three dimensional grid, stored in `x`, then `y`, then `z` order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T: Clone + Default + PartialEq> {
+    data: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl<T: Clone + Default + PartialEq> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, default: T) -> Self {
+        Self {
+            data: vec![default; width * height * depth],
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn new_default(width: usize, height: usize, depth: usize) -> Self {
+        Self::new(width, height, depth, T::default())
+    }
+
+    /// A grid just large enough to hold every point, with those points set to `value` and the
+    /// rest to the default.
+    pub fn from_points(points: impl IntoIterator<Item = Pos>, value: T) -> Self {
+        let points: Vec<Pos> = points.into_iter().collect();
+        let (width, height, depth) = match Pos::bounds(points.iter().copied()) {
+            Some((_, max)) => (max.x + 1, max.y + 1, max.z + 1),
+            None => (0, 0, 0),
+        };
+        let mut grid = Self::new_default(width, height, depth);
+        for p in points {
+            grid[p] = value.clone();
+        }
+        grid
+    }
+
+    fn index_of(&self, p: Pos) -> usize {
+        assert!(self.in_bounds(p), "{p:?} is outside the grid");
+        (p.z * self.height + p.y) * self.width + p.x
+    }
+
+    fn pos_of(&self, index: usize) -> Pos {
+        Pos::new(
+            index % self.width,
+            index / self.width % self.height,
+            index / (self.width * self.height),
+        )
+    }
+
+    pub fn get(&self, p: Pos) -> &T {
+        &self.data[self.index_of(p)]
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> &mut T {
+        let index = self.index_of(p);
+        &mut self.data[index]
+    }
+
+    pub fn set(&mut self, p: Pos, value: T) {
+        let index = self.index_of(p);
+        self.data[index] = value;
+    }
+
+    pub fn in_bounds(&self, p: Pos) -> bool {
+        p.x < self.width && p.y < self.height && p.z < self.depth
+    }
+
+    /// The cell at `p`, or `None` if it is outside the grid.
+    pub fn try_get(&self, p: Pos) -> Option<&T> {
+        self.in_bounds(p).then(|| self.get(p))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.pos_of(i), value))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn neighbors(&'_ self, center: Pos, connectivity: Connectivity3) -> Grid3Neighbors<'_, T> {
+        let points = match connectivity {
+            Connectivity3::Faces => center.neighbors6(),
+            Connectivity3::FacesAndEdges => center.neighbors18(),
+            Connectivity3::All => center.neighbors26(),
+        };
+        Grid3Neighbors { grid: self, points }
+    }
+
+    pub fn face_neighbors(&'_ self, center: Pos) -> Grid3Neighbors<'_, T> {
+        self.neighbors(center, Connectivity3::Faces)
+    }
+
+    /// The points reachable from `start` through face-adjacent cells matching `predicate`,
+    /// including `start` itself.  Empty if `start` does not match.
+    pub fn flood_fill<F>(&self, start: Pos, predicate: F) -> Vec<Pos>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        self.flood_fill_with(start, Connectivity3::Faces, predicate)
+    }
+
+    pub fn flood_fill_with<F>(
+        &self,
+        start: Pos,
+        connectivity: Connectivity3,
+        mut predicate: F,
+    ) -> Vec<Pos>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        if !predicate(start, &self[start]) {
+            return Vec::new();
+        }
+        let mut visited = Grid3::new(self.width, self.height, self.depth, false);
+        visited[start] = true;
+        let mut filled = vec![start];
+        let mut next = 0;
+        while next < filled.len() {
+            let current = filled[next];
+            next += 1;
+            for (p, value) in self.neighbors(current, connectivity) {
+                if !visited[p] && predicate(p, value) {
+                    visited[p] = true;
+                    filled.push(p);
+                }
+            }
+        }
+        filled
+    }
+
+    /// The number of faces of solid cells that do not touch another solid cell, including the
+    /// faces around any enclosed air pockets.
+    pub fn surface_area<F: Fn(&T) -> bool>(&self, is_solid: F) -> usize {
+        self.count_faces(&is_solid, |_| true)
+    }
+
+    /// The number of faces of solid cells that can be reached from outside the grid through
+    /// face-adjacent air, so enclosed air pockets are ignored.
+    pub fn exterior_surface_area<F: Fn(&T) -> bool>(&self, is_solid: F) -> usize {
+        let mut outside = Grid3::new(self.width, self.height, self.depth, false);
+        let mut queue = Vec::new();
+        for (p, value) in self.iter() {
+            let on_boundary = p.x == 0
+                || p.y == 0
+                || p.z == 0
+                || p.x == self.width - 1
+                || p.y == self.height - 1
+                || p.z == self.depth - 1;
+            if on_boundary && !is_solid(value) {
+                outside[p] = true;
+                queue.push(p);
+            }
+        }
+        while let Some(current) = queue.pop() {
+            for (p, value) in self.face_neighbors(current) {
+                if !outside[p] && !is_solid(value) {
+                    outside[p] = true;
+                    queue.push(p);
+                }
+            }
+        }
+        self.count_faces(&is_solid, |p| outside[p])
+    }
+
+    /// Counts faces of solid cells whose neighbour is outside the grid or is air accepted by
+    /// `counts_air`.
+    fn count_faces<F, G>(&self, is_solid: &F, counts_air: G) -> usize
+    where
+        F: Fn(&T) -> bool,
+        G: Fn(Pos) -> bool,
+    {
+        self.iter()
+            .filter(|(_, value)| is_solid(value))
+            .map(|(p, _)| {
+                let inside = self.face_neighbors(p).count();
+                let exposed = self
+                    .face_neighbors(p)
+                    .filter(|(n, value)| !is_solid(value) && counts_air(*n))
+                    .count();
+                6 - inside + exposed
+            })
+            .sum()
+    }
+
+    /// The layer at height `z` as text, one line per row, each line ending in a newline.
+    pub fn render_slice<F: Fn(&T) -> char>(&self, z: usize, cell: F) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                result.push(cell(self.get(Pos::new(x, y, z))));
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+/// The in-bounds neighbours of a cell in a `Grid3`.
+pub struct Grid3Neighbors<'a, T: Clone + Default + PartialEq> {
+    grid: &'a Grid3<T>,
+    points: Neighbors3<usize>,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for Grid3Neighbors<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let grid = self.grid;
+        self.points
+            .by_ref()
+            .find(|p| grid.in_bounds(*p))
+            .map(|p| (p, grid.get(p)))
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Index<Pos> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, p: Pos) -> &Self::Output {
+        self.get(p)
+    }
+}
+
+impl<T: Clone + Default + PartialEq> IndexMut<Pos> for Grid3<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut Self::Output {
+        self.get_mut(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DROPLET: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    fn droplet() -> Grid3<bool> {
+        let points = DROPLET.lines().map(|line| {
+            let c: Vec<usize> = line.split(',').map(|n| n.parse().unwrap()).collect();
+            Pos::new(c[0], c[1], c[2])
+        });
+        Grid3::from_points(points, true)
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let grid = droplet();
+        assert_eq!((grid.width, grid.height, grid.depth), (4, 4, 7));
+        assert_eq!(grid.iter().filter(|(_, v)| **v).count(), 13);
+        assert_eq!(grid.surface_area(|&v| v), 64);
+        assert_eq!(grid.exterior_surface_area(|&v| v), 58);
+
+        let mut pair = Grid3::new(2, 1, 1, true);
+        assert_eq!(pair.surface_area(|&v| v), 10);
+        pair[Pos::new(1, 0, 0)] = false;
+        assert_eq!(pair.exterior_surface_area(|&v| v), 6);
+    }
+
+    #[test]
+    fn test_neighbors_and_flood_fill() {
+        let grid = Grid3::new(3, 3, 3, 0u8);
+        let corner = Pos::new(0, 0, 0);
+        let centre = Pos::new(1, 1, 1);
+        assert_eq!(grid.face_neighbors(corner).count(), 3);
+        assert_eq!(grid.neighbors(corner, Connectivity3::All).count(), 7);
+        assert_eq!(
+            grid.neighbors(centre, Connectivity3::FacesAndEdges).count(),
+            18
+        );
+        assert_eq!(grid.neighbors(centre, Connectivity3::All).count(), 26);
+        assert_eq!(grid.try_get(Pos::new(3, 0, 0)), None);
+
+        let droplet = droplet();
+        let pocket = droplet.flood_fill(Pos::new(2, 2, 5), |_, &v| !v);
+        assert_eq!(pocket, [Pos::new(2, 2, 5)]);
+        assert_eq!(droplet.flood_fill(Pos::new(2, 2, 2), |_, &v| v).len(), 8);
+        assert!(droplet.flood_fill(Pos::new(0, 0, 0), |_, &v| v).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn test_out_of_bounds() {
+        let grid = Grid3::new(3, 3, 3, 0u8);
+        let _ = grid[Pos::new(3, 0, 0)];
+    }
+
+    #[test]
+    fn test_render_slice() {
+        let grid = droplet();
+        assert_eq!(
+            grid.render_slice(2, |&v| if v { '#' } else { '.' }),
+            "....\n..#.\n.###\n..#.\n"
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+/// Axial coordinates on a grid of pointy-topped hexagons.  `q` increases to the east and `r`
+/// to the south east, so rows of constant `r` run east to west and `r` increases downwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Axial {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// Cube coordinates, the axial coordinates with the redundant `s = -q - r` added so that the
+/// three axes are symmetric.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cube {
+    pub q: isize,
+    pub r: isize,
+    pub s: isize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDirection {
+    #[default]
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+/// A change of direction by a multiple of 60°, the hex counterpart of `grid::Turn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexTurn {
+    Straight = 0,
+    Right,
+    SharpRight,
+    Reverse,
+    SharpLeft,
+    Left,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HexParseError {
+    #[error("{0:?} is not a hex direction")]
+    Direction(String),
+    #[error("unexpected {found:?} at byte {index}")]
+    Step { index: usize, found: char },
+}
+
+impl HexDirection {
+    /// Clockwise from east.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Rotates clockwise by `steps` multiples of 60°; negative steps rotate anticlockwise.
+    pub fn rotate(&self, steps: i32) -> Self {
+        Self::ALL[(*self as i32 + steps).rem_euclid(6) as usize]
+    }
+    pub fn left(&self) -> Self {
+        self.rotate(-1)
+    }
+    pub fn right(&self) -> Self {
+        self.rotate(1)
+    }
+    pub fn reverse(&self) -> Self {
+        self.rotate(3)
+    }
+    pub fn turn(&self, turn: HexTurn) -> Self {
+        self.rotate(turn as i32)
+    }
+    pub const fn to_axial(self) -> Axial {
+        match self {
+            HexDirection::East => Axial::new(1, 0),
+            HexDirection::SouthEast => Axial::new(0, 1),
+            HexDirection::SouthWest => Axial::new(-1, 1),
+            HexDirection::West => Axial::new(-1, 0),
+            HexDirection::NorthWest => Axial::new(0, -1),
+            HexDirection::NorthEast => Axial::new(1, -1),
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HexDirection::East => "e",
+            HexDirection::SouthEast => "se",
+            HexDirection::SouthWest => "sw",
+            HexDirection::West => "w",
+            HexDirection::NorthWest => "nw",
+            HexDirection::NorthEast => "ne",
+        }
+    }
+
+    /// Parses a run of steps written without separators, such as `nwwswee`.  Commas and
+    /// whitespace between steps are ignored.
+    pub fn parse_steps(s: &str) -> Result<Vec<HexDirection>, HexParseError> {
+        let mut steps = Vec::new();
+        let mut chars = s.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            let step = match c {
+                ',' => continue,
+                c if c.is_whitespace() => continue,
+                'e' => HexDirection::East,
+                'w' => HexDirection::West,
+                'n' | 's' => match (c, chars.next()) {
+                    ('n', Some((_, 'e'))) => HexDirection::NorthEast,
+                    ('n', Some((_, 'w'))) => HexDirection::NorthWest,
+                    ('s', Some((_, 'e'))) => HexDirection::SouthEast,
+                    ('s', Some((_, 'w'))) => HexDirection::SouthWest,
+                    (_, Some((index, found))) => return Err(HexParseError::Step { index, found }),
+                    (found, None) => return Err(HexParseError::Step { index, found }),
+                },
+                found => return Err(HexParseError::Step { index, found }),
+            };
+            steps.push(step);
+        }
+        Ok(steps)
+    }
+}
+
+impl Add<HexTurn> for HexDirection {
+    type Output = HexDirection;
+
+    fn add(self, rhs: HexTurn) -> Self::Output {
+        self.turn(rhs)
+    }
+}
+
+impl AddAssign<HexTurn> for HexDirection {
+    fn add_assign(&mut self, rhs: HexTurn) {
+        *self = *self + rhs;
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = HexParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HexDirection::ALL
+            .into_iter()
+            .find(|d| d.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| HexParseError::Direction(String::from(s)))
+    }
+}
+
+impl Display for HexDirection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Axial {
+    pub const ORIGIN: Axial = Axial::new(0, 0);
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn neighbor(self, direction: HexDirection) -> Self {
+        self + direction.to_axial()
+    }
+
+    /// The six adjacent hexes, clockwise from east.
+    pub fn neighbors(self) -> impl Iterator<Item = Axial> {
+        HexDirection::ALL.into_iter().map(move |d| self.neighbor(d))
+    }
+
+    /// The number of steps between two hexes.
+    pub fn distance(&self, other: &Axial) -> isize {
+        let d = *self - *other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// The hexes at exactly `radius` steps, starting at the one `radius` steps west and going
+    /// clockwise.  A ring of radius 0 is just the centre.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Axial> {
+        let start = self + HexDirection::West.to_axial() * radius as isize;
+        let sides = HexDirection::ALL.into_iter().cycle().skip(5).take(6);
+        let steps = sides.flat_map(move |d| std::iter::repeat_n(d, radius));
+        std::iter::once(self)
+            .filter(move |_| radius == 0)
+            .chain(steps.scan(start, |hex, d| {
+                let current = *hex;
+                *hex = hex.neighbor(d);
+                Some(current)
+            }))
+    }
+
+    /// The hexes within `radius` steps, ring by ring outwards from the centre.
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = Axial> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+}
+
+impl From<Cube> for Axial {
+    fn from(c: Cube) -> Self {
+        Axial::new(c.q, c.r)
+    }
+}
+
+impl From<HexDirection> for Axial {
+    fn from(d: HexDirection) -> Self {
+        d.to_axial()
+    }
+}
+
+impl Add for Axial {
+    type Output = Axial;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Axial::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Axial {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<HexDirection> for Axial {
+    type Output = Axial;
+
+    fn add(self, rhs: HexDirection) -> Self::Output {
+        self.neighbor(rhs)
+    }
+}
+
+impl AddAssign<HexDirection> for Axial {
+    fn add_assign(&mut self, rhs: HexDirection) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Axial {
+    type Output = Axial;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Axial::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Neg for Axial {
+    type Output = Axial;
+
+    fn neg(self) -> Self::Output {
+        Axial::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<isize> for Axial {
+    type Output = Axial;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Axial::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Cube {
+    pub const fn new(q: isize, r: isize, s: isize) -> Self {
+        Self { q, r, s }
+    }
+
+    pub fn distance(&self, other: &Cube) -> isize {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s - other.s).abs()) / 2
+    }
+
+    /// Rotates 60° clockwise about the origin.
+    pub fn rotate_clockwise(&self) -> Self {
+        Cube::new(-self.r, -self.s, -self.q)
+    }
+
+    /// Rotates 60° anticlockwise about the origin.
+    pub fn rotate_anticlockwise(&self) -> Self {
+        Cube::new(-self.s, -self.q, -self.r)
+    }
+}
+
+impl From<Axial> for Cube {
+    fn from(a: Axial) -> Self {
+        Cube::new(a.q, a.r, a.s())
+    }
+}
+
+/// Dense storage for the hexes in a parallelogram of axial coordinates: `q` from `min.q` for
+/// `width` hexes and `r` from `min.r` for `height` hexes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HexGrid<T: Clone + Default + PartialEq> {
+    data: Vec<T>,
+    pub min: Axial,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T: Clone + Default + PartialEq> HexGrid<T> {
+    pub fn new(min: Axial, width: usize, height: usize, default: T) -> Self {
+        Self {
+            data: vec![default; width * height],
+            min,
+            width,
+            height,
+        }
+    }
+
+    pub fn new_default(min: Axial, width: usize, height: usize) -> Self {
+        Self::new(min, width, height, T::default())
+    }
+
+    /// A grid holding every hex within `radius` steps of the origin, along with the corners of
+    /// the enclosing parallelogram.
+    pub fn with_radius(radius: usize, default: T) -> Self {
+        let side = 2 * radius + 1;
+        let r = radius as isize;
+        Self::new(Axial::new(-r, -r), side, side, default)
+    }
+
+    fn index_of(&self, hex: Axial) -> Option<usize> {
+        let q = usize::try_from(hex.q - self.min.q).ok()?;
+        let r = usize::try_from(hex.r - self.min.r).ok()?;
+        (q < self.width && r < self.height).then_some(r * self.width + q)
+    }
+
+    pub fn in_bounds(&self, hex: Axial) -> bool {
+        self.index_of(hex).is_some()
+    }
+
+    pub fn get(&self, hex: Axial) -> Option<&T> {
+        self.index_of(hex).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, hex: Axial) -> Option<&mut T> {
+        self.index_of(hex).map(|i| &mut self.data[i])
+    }
+
+    /// Sets a hex, panicking if it is outside the grid.
+    pub fn set(&mut self, hex: Axial, value: T) {
+        self[hex] = value;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Axial, &T)> {
+        self.data.iter().enumerate().map(|(i, value)| {
+            let q = self.min.q + (i % self.width) as isize;
+            let r = self.min.r + (i / self.width) as isize;
+            (Axial::new(q, r), value)
+        })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// The in-bounds neighbours of `center`, clockwise from east.
+    pub fn neighbors(&'_ self, center: Axial) -> HexNeighbors<'_, T> {
+        HexNeighbors {
+            grid: self,
+            center,
+            current_direction: 0,
+        }
+    }
+}
+
+pub struct HexNeighbors<'a, T: Clone + Default + PartialEq> {
+    grid: &'a HexGrid<T>,
+    center: Axial,
+    current_direction: usize,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Iterator for HexNeighbors<'a, T> {
+    type Item = (Axial, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current_direction < HexDirection::ALL.len() {
+            let direction = HexDirection::ALL[self.current_direction];
+            self.current_direction += 1;
+            let hex = self.center.neighbor(direction);
+            if let Some(value) = self.grid.get(hex) {
+                return Some((hex, value));
+            }
+        }
+        None
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Index<Axial> for HexGrid<T> {
+    type Output = T;
+
+    fn index(&self, hex: Axial) -> &Self::Output {
+        self.get(hex).expect("hex outside the grid")
+    }
+}
+
+impl<T: Clone + Default + PartialEq> IndexMut<Axial> for HexGrid<T> {
+    fn index_mut(&mut self, hex: Axial) -> &mut Self::Output {
+        self.get_mut(hex).expect("hex outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn walk(steps: &str) -> Axial {
+        HexDirection::parse_steps(steps)
+            .unwrap()
+            .into_iter()
+            .fold(Axial::ORIGIN, |hex, d| hex + d)
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(HexDirection::East + HexTurn::Left, HexDirection::NorthEast);
+        assert_eq!(HexDirection::NorthEast.right(), HexDirection::East);
+        assert_eq!(HexDirection::SouthWest.reverse(), HexDirection::NorthEast);
+        assert_eq!(
+            HexDirection::West.turn(HexTurn::SharpRight),
+            HexDirection::East.rotate(5)
+        );
+        for d in HexDirection::ALL {
+            assert_eq!(d.to_axial() + d.reverse().to_axial(), Axial::ORIGIN);
+            assert_eq!(d.as_str().parse(), Ok(d));
+            let cube = Cube::from(d.to_axial());
+            assert_eq!(Axial::from(cube.rotate_clockwise()), d.right().to_axial());
+            assert_eq!(
+                Axial::from(cube.rotate_anticlockwise()),
+                d.left().to_axial()
+            );
+        }
+        assert!("n".parse::<HexDirection>().is_err());
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(walk("esew"), HexDirection::SouthEast.to_axial());
+        assert_eq!(walk("nwwswee"), Axial::ORIGIN);
+        assert_eq!(walk("ne,ne, ne"), Axial::new(3, -3));
+        assert_eq!(walk("ne,ne,ne").distance(&Axial::ORIGIN), 3);
+        assert_eq!(
+            HexDirection::parse_steps("nex"),
+            Err(HexParseError::Step {
+                index: 2,
+                found: 'x'
+            })
+        );
+        assert!(HexDirection::parse_steps("ns").is_err());
+        assert!(HexDirection::parse_steps("s").is_err());
+    }
+
+    #[test]
+    fn test_rings() {
+        let center = Axial::new(2, -1);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), [center]);
+        for radius in 1..5 {
+            let ring: Vec<_> = center.ring(radius).collect();
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(&center) == radius as isize));
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+            assert_eq!(ring.last().unwrap().distance(&ring[0]), 1);
+        }
+        let spiral: HashSet<_> = center.spiral(3).collect();
+        assert_eq!(spiral.len(), 1 + 3 * 3 * 4);
+        assert_eq!(Cube::from(center).distance(&Cube::default()), 2);
+    }
+
+    #[test]
+    fn test_hex_grid() {
+        let mut grid = HexGrid::with_radius(2, false);
+        assert!(grid.in_bounds(Axial::new(-2, -2)));
+        assert!(!grid.in_bounds(Axial::new(3, 0)));
+        assert_eq!(grid.get(Axial::new(0, 3)), None);
+        assert_eq!(grid.neighbors(Axial::ORIGIN).count(), 6);
+        assert_eq!(grid.neighbors(Axial::new(2, 2)).count(), 2);
+        assert_eq!(grid.neighbors(Axial::new(2, 0)).count(), 4);
+
+        for hex in Axial::ORIGIN.ring(1) {
+            grid[hex] = true;
+        }
+        grid.set(Axial::new(2, 0), true);
+        let black = |hex| grid.neighbors(hex).filter(|(_, b)| **b).count();
+        assert_eq!(black(Axial::ORIGIN), 6);
+        assert_eq!(black(Axial::new(1, 0)), 3);
+        assert_eq!(grid.iter().filter(|(_, b)| **b).count(), 7);
+        assert_eq!(grid.iter().next(), Some((Axial::new(-2, -2), &false)));
+    }
+}
@@ -2,12 +2,17 @@ use regex::Regex;
 use std::str::FromStr;
 
 pub mod bit_flags;
+pub mod cycle;
 pub mod fixed_queue;
 pub mod grid;
+pub mod grid3;
+pub mod hex;
 pub mod indexed_key_table;
 pub mod integer_interval;
 pub mod intersect_sorted_iterators;
 pub mod md5;
+pub mod pathfinding;
+pub mod polygon;
 pub mod pos;
 pub mod pos3d;
 pub mod smallest_items;
@@ -0,0 +1,517 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+use crate::grid::{Direction, Grid, Point, Turn};
+
+/// The result of a successful search: the total cost of the route and every point on it,
+/// starting with the start point and ending with the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub distance: usize,
+    pub points: Vec<Point>,
+}
+
+fn reconstruct_path(previous: &Grid<Option<Point>>, start: Point, goal: Point) -> Vec<Point> {
+    let mut points = vec![goal];
+    let mut current = goal;
+    while current != start {
+        current = previous[current].expect("every reached point except the start has a parent");
+        points.push(current);
+    }
+    points.reverse();
+    points
+}
+
+/// Breadth first search where every step costs one.
+///
+/// `passable` is called with the current cell and a neighbouring cell and decides whether the
+/// move is allowed, so one-way or height-limited moves can be expressed.
+pub fn bfs<T, G, P>(grid: &Grid<T>, start: Point, mut is_goal: G, mut passable: P) -> Option<Path>
+where
+    T: Clone + Default + PartialEq,
+    G: FnMut(Point, &T) -> bool,
+    P: FnMut((Point, &T), (Point, &T)) -> bool,
+{
+    let mut distance = Grid::new(grid.width, grid.height, usize::MAX);
+    let mut previous = Grid::new(grid.width, grid.height, None);
+    let mut queue = VecDeque::new();
+    distance[start] = 0;
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        if is_goal(current, &grid[current]) {
+            return Some(Path {
+                distance: distance[current],
+                points: reconstruct_path(&previous, start, current),
+            });
+        }
+        for (next, value) in grid.orthogonal_neighbors(current) {
+            if distance[next] == usize::MAX && passable((current, &grid[current]), (next, value)) {
+                distance[next] = distance[current] + 1;
+                previous[next] = Some(current);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm for weighted moves.
+///
+/// `cost` returns the cost of moving from the first cell to the second, or `None` if the move
+/// is not allowed.
+pub fn dijkstra<T, G, C>(grid: &Grid<T>, start: Point, is_goal: G, cost: C) -> Option<Path>
+where
+    T: Clone + Default + PartialEq,
+    G: FnMut(Point, &T) -> bool,
+    C: FnMut((Point, &T), (Point, &T)) -> Option<usize>,
+{
+    best_first(grid, start, is_goal, cost, |_| 0)
+}
+
+/// A* search towards a single goal, using the manhattan distance as the heuristic.
+///
+/// The heuristic is only admissible when every move costs at least one, so `cost` should not
+/// return `Some(0)`.
+pub fn astar<T, C>(grid: &Grid<T>, start: Point, goal: Point, cost: C) -> Option<Path>
+where
+    T: Clone + Default + PartialEq,
+    C: FnMut((Point, &T), (Point, &T)) -> Option<usize>,
+{
+    best_first(grid, start, |p, _| p == goal, cost, |p| p.manhattan(&goal))
+}
+
+fn best_first<T, G, C, H>(
+    grid: &Grid<T>,
+    start: Point,
+    mut is_goal: G,
+    mut cost: C,
+    heuristic: H,
+) -> Option<Path>
+where
+    T: Clone + Default + PartialEq,
+    G: FnMut(Point, &T) -> bool,
+    C: FnMut((Point, &T), (Point, &T)) -> Option<usize>,
+    H: Fn(Point) -> usize,
+{
+    let mut distance = Grid::new(grid.width, grid.height, usize::MAX);
+    let mut previous = Grid::new(grid.width, grid.height, None);
+    let mut heap = BinaryHeap::new();
+    distance[start] = 0;
+    heap.push(Reverse((heuristic(start), 0, start.y, start.x)));
+
+    while let Some(Reverse((_, current_distance, y, x))) = heap.pop() {
+        let current = Point::new(x, y);
+        if current_distance > distance[current] {
+            continue;
+        }
+        if is_goal(current, &grid[current]) {
+            return Some(Path {
+                distance: current_distance,
+                points: reconstruct_path(&previous, start, current),
+            });
+        }
+        for (next, value) in grid.orthogonal_neighbors(current) {
+            let Some(step) = cost((current, &grid[current]), (next, value)) else {
+                continue;
+            };
+            let next_distance = current_distance + step;
+            if next_distance < distance[next] {
+                distance[next] = next_distance;
+                previous[next] = Some(current);
+                heap.push(Reverse((
+                    next_distance + heuristic(next),
+                    next_distance,
+                    next.y,
+                    next.x,
+                )));
+            }
+        }
+    }
+    None
+}
+
+/// A search state for movement where the facing matters.  `run` counts the straight moves made
+/// since the last turn; it is capped at the largest limit in the `TurnCosts`, beyond which its
+/// exact value makes no difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectedState {
+    pub point: Point,
+    pub direction: Direction,
+    pub run: usize,
+}
+
+/// Costs and limits for `dijkstra_directed`.
+///
+/// Every move turns (possibly `Turn::Straight`) and then steps one cell in the new direction,
+/// costing `forward` plus the cost of the turn.  A turn whose cost is `None` is not allowed.
+/// Turning is only allowed after at least `min_straight` moves in the current direction, and
+/// no more than `max_straight` moves may be made without turning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurnCosts {
+    pub forward: usize,
+    pub turns: [Option<usize>; 4],
+    pub min_straight: usize,
+    pub max_straight: Option<usize>,
+}
+
+impl Default for TurnCosts {
+    fn default() -> Self {
+        Self {
+            forward: 1,
+            turns: [Some(0), Some(0), None, Some(0)],
+            min_straight: 0,
+            max_straight: None,
+        }
+    }
+}
+
+impl TurnCosts {
+    pub fn turn_cost(&self, turn: Turn) -> Option<usize> {
+        self.turns[turn as usize]
+    }
+    fn run_cap(&self) -> usize {
+        self.max_straight.unwrap_or(0).max(self.min_straight)
+    }
+}
+
+/// Every optimal route found by `dijkstra_directed`, stored as the predecessor graph of the
+/// search so that ties are not lost.
+#[derive(Debug, Clone)]
+pub struct BestPaths {
+    pub cost: usize,
+    pub ends: Vec<DirectedState>,
+    starts: HashSet<DirectedState>,
+    predecessors: HashMap<DirectedState, Vec<DirectedState>>,
+}
+
+impl BestPaths {
+    /// Every point that lies on at least one optimal route.
+    pub fn tiles(&self) -> HashSet<Point> {
+        let mut seen: HashSet<DirectedState> = self.ends.iter().copied().collect();
+        let mut stack = self.ends.clone();
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(*previous) {
+                    stack.push(*previous);
+                }
+            }
+        }
+        seen.into_iter().map(|state| state.point).collect()
+    }
+
+    /// Every distinct optimal route, each running from a start point to a goal point.  When
+    /// moves can cost nothing the predecessor graph can contain cycles, so no route visits the
+    /// same state twice.
+    pub fn paths(&self) -> Vec<Vec<Point>> {
+        fn walk(
+            best: &BestPaths,
+            state: DirectedState,
+            route: &mut Vec<DirectedState>,
+            paths: &mut HashSet<Vec<Point>>,
+        ) {
+            route.push(state);
+            if best.starts.contains(&state) {
+                paths.insert(route.iter().rev().map(|s| s.point).collect());
+            } else {
+                for &p in best.predecessors.get(&state).into_iter().flatten() {
+                    if !route.contains(&p) {
+                        walk(best, p, route, paths);
+                    }
+                }
+            }
+            route.pop();
+        }
+
+        let mut paths = HashSet::new();
+        for &end in &self.ends {
+            walk(self, end, &mut Vec::new(), &mut paths);
+        }
+        paths.into_iter().collect()
+    }
+}
+
+/// Dijkstra's algorithm over `(Point, Direction)` states, for movement where turning costs
+/// something or straight runs are limited.
+///
+/// `cost` is added to `TurnCosts::forward` for each move, and returns `None` if the move is not
+/// allowed.  A goal is only accepted once `TurnCosts::min_straight` has been satisfied.
+pub fn dijkstra_directed<T, S, G, C>(
+    grid: &Grid<T>,
+    starts: S,
+    mut is_goal: G,
+    config: TurnCosts,
+    mut cost: C,
+) -> Option<BestPaths>
+where
+    T: Clone + Default + PartialEq,
+    S: IntoIterator<Item = (Point, Direction)>,
+    G: FnMut(Point, &T) -> bool,
+    C: FnMut((Point, &T), (Point, &T)) -> Option<usize>,
+{
+    const TURNS: [Turn; 4] = [Turn::Straight, Turn::Right, Turn::Reverse, Turn::Left];
+    let cap = config.run_cap();
+    let mut distance: HashMap<DirectedState, usize> = HashMap::new();
+    let mut predecessors: HashMap<DirectedState, Vec<DirectedState>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    let mut start_states = HashSet::new();
+    for (point, direction) in starts {
+        let state = DirectedState {
+            point,
+            direction,
+            run: 0,
+        };
+        start_states.insert(state);
+        distance.insert(state, 0);
+        heap.push(Reverse((0, point.y, point.x, direction, 0)));
+    }
+
+    let mut best: Option<usize> = None;
+    let mut ends = Vec::new();
+    while let Some(Reverse((current_cost, y, x, direction, run))) = heap.pop() {
+        if best.is_some_and(|b| current_cost > b) {
+            break;
+        }
+        let state = DirectedState {
+            point: Point::new(x, y),
+            direction,
+            run,
+        };
+        if current_cost > distance[&state] {
+            continue;
+        }
+        if run >= config.min_straight && is_goal(state.point, &grid[state.point]) {
+            best = Some(current_cost);
+            ends.push(state);
+            continue;
+        }
+        for turn in TURNS {
+            let Some(turn_cost) = config.turn_cost(turn) else {
+                continue;
+            };
+            let straight = turn == Turn::Straight;
+            if straight && config.max_straight.is_some_and(|max| run >= max) {
+                continue;
+            }
+            if !straight && run < config.min_straight {
+                continue;
+            }
+            let direction = direction.turn(turn);
+            let Some(next) = grid.add_direction(state.point, direction) else {
+                continue;
+            };
+            let Some(step) = cost((state.point, &grid[state.point]), (next, &grid[next])) else {
+                continue;
+            };
+            let next_state = DirectedState {
+                point: next,
+                direction,
+                run: if straight { run + 1 } else { 1 }.min(cap),
+            };
+            let next_cost = current_cost + config.forward + turn_cost + step;
+            match distance.get(&next_state) {
+                Some(&d) if d < next_cost => {}
+                Some(&d) if d == next_cost => {
+                    predecessors.entry(next_state).or_default().push(state);
+                }
+                _ => {
+                    distance.insert(next_state, next_cost);
+                    predecessors.insert(next_state, vec![state]);
+                    heap.push(Reverse((
+                        next_cost,
+                        next.y,
+                        next.x,
+                        direction,
+                        next_state.run,
+                    )));
+                }
+            }
+        }
+    }
+    best.map(|cost| BestPaths {
+        cost,
+        ends,
+        starts: start_states,
+        predecessors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn open(_: (Point, &u8), (_, &to): (Point, &u8)) -> bool {
+        to != b'#'
+    }
+
+    fn weight(_: (Point, &u8), (_, &to): (Point, &u8)) -> Option<usize> {
+        match to {
+            b'#' => None,
+            b'0'..=b'9' => Some((to - b'0') as usize),
+            _ => Some(1),
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::from(MAZE);
+        let start = grid.find(b'S').unwrap();
+        let path = bfs(&grid, start, |_, &c| c == b'E', open).unwrap();
+        assert_eq!(path.distance, 15);
+        assert_eq!(path.points.len(), 16);
+        assert_eq!(path.points.first(), Some(&start));
+        assert_eq!(path.points.last(), grid.find(b'E').as_ref());
+        for pair in path.points.windows(2) {
+            assert_eq!(pair[0].manhattan(&pair[1]), 1);
+        }
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let grid = Grid::from("S#.\n##.\n..E");
+        let start = grid.find(b'S').unwrap();
+        let goal = grid.find(b'E').unwrap();
+        assert_eq!(bfs(&grid, start, |p, _| p == goal, open), None);
+        assert_eq!(dijkstra(&grid, start, |p, _| p == goal, weight), None);
+        assert_eq!(astar(&grid, start, goal, weight), None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let grid = Grid::from("S9E\n1#1\n111");
+        let start = Point::new(0, 0);
+        let goal = Point::new(2, 0);
+        let path = dijkstra(&grid, start, |p, _| p == goal, weight).unwrap();
+        assert_eq!(path.distance, 6);
+        assert_eq!(path, astar(&grid, start, goal, weight).unwrap());
+        assert_eq!(path.points.len(), 7);
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let grid = Grid::from(MAZE);
+        let path = astar(&grid, Point::new(0, 0), Point::new(0, 0), weight).unwrap();
+        assert_eq!(path.distance, 0);
+        assert_eq!(path.points, vec![Point::new(0, 0)]);
+    }
+
+    const REINDEER: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn test_directed_turn_costs() {
+        let grid = Grid::from(REINDEER);
+        let start = grid.find(b'S').unwrap();
+        let config = TurnCosts {
+            turns: [Some(0), Some(1000), Some(2000), Some(1000)],
+            ..TurnCosts::default()
+        };
+        let best = dijkstra_directed(
+            &grid,
+            [(start, Direction::East)],
+            |_, &c| c == b'E',
+            config,
+            |_, (_, &to)| (to != b'#').then_some(0),
+        )
+        .unwrap();
+        assert_eq!(best.cost, 7036);
+        assert_eq!(best.tiles().len(), 45);
+        assert_eq!(best.paths().len(), 3);
+    }
+
+    const CRUCIBLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_directed_straight_limits() {
+        let grid = Grid::from(CRUCIBLE);
+        let start = Point::new(0, 0);
+        let goal = Point::new(grid.width - 1, grid.height - 1);
+        let search = |min_straight, max_straight| {
+            let config = TurnCosts {
+                forward: 0,
+                min_straight,
+                max_straight: Some(max_straight),
+                ..TurnCosts::default()
+            };
+            dijkstra_directed(
+                &grid,
+                [(start, Direction::East), (start, Direction::South)],
+                |p, _| p == goal,
+                config,
+                weight,
+            )
+            .unwrap()
+            .cost
+        };
+        assert_eq!(search(0, 3), 102);
+        assert_eq!(search(4, 10), 94);
+    }
+
+    #[test]
+    fn test_directed_zero_cost_cycles() {
+        let grid = Grid::new(3, 3, ());
+        let config = TurnCosts {
+            forward: 0,
+            ..TurnCosts::default()
+        };
+        let goal = Point::new(2, 2);
+        let best = dijkstra_directed(
+            &grid,
+            [(Point::new(0, 0), Direction::East)],
+            |p, _| p == goal,
+            config,
+            |_, _| Some(0),
+        )
+        .unwrap();
+        assert_eq!(best.cost, 0);
+        let paths = best.paths();
+        assert!(!paths.is_empty());
+        for path in &paths {
+            assert_eq!(path.first(), Some(&Point::new(0, 0)));
+            assert_eq!(path.last(), Some(&goal));
+        }
+        assert!(paths.contains(&vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(2, 1),
+            goal
+        ]));
+    }
+}
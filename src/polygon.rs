@@ -0,0 +1,183 @@
+use num::Integer;
+
+use crate::{
+    grid::{Direction, Vector},
+    pos::Position,
+};
+
+/// A closed polygon on the integer lattice.  The last vertex joins back to the first.
+///
+/// Coordinates follow the grid convention of `y` increasing downwards, so a polygon that turns
+/// right as it is walked (`East` then `South`) is clockwise.  Sums are computed in `i128` so
+/// very large coordinates cannot overflow.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    vertices: Vec<Position<i64>>,
+}
+
+impl Polygon {
+    pub fn from_vertices(vertices: impl IntoIterator<Item = Position<i64>>) -> Self {
+        Self {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+
+    /// The polygon traced by walking the `moves` from the origin, e.g. a dig plan.
+    pub fn from_moves(moves: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        Self::from_steps(
+            moves
+                .into_iter()
+                .map(|(direction, length)| (Vector::from_direction(direction), length)),
+        )
+    }
+
+    /// The polygon traced by walking from the origin, taking each vector the given number of
+    /// times.
+    pub fn from_steps(steps: impl IntoIterator<Item = (Vector, i64)>) -> Self {
+        let mut current = Position::new(0i64, 0);
+        let mut vertices = vec![current];
+        for (step, length) in steps {
+            current += Position::new(step.x as i64, step.y as i64) * length;
+            vertices.push(current);
+        }
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Position<i64>] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Position<i64>, Position<i64>)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+    }
+
+    /// Twice the signed area from the shoelace formula; positive when clockwise.
+    pub fn double_signed_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum()
+    }
+
+    /// Twice the enclosed area, which is always an integer for a lattice polygon.
+    pub fn double_area(&self) -> i128 {
+        self.double_signed_area().abs()
+    }
+
+    /// The enclosed area, rounded down when it is a half integer.
+    pub fn area(&self) -> i128 {
+        self.double_area() / 2
+    }
+
+    pub fn is_clockwise(&self) -> bool {
+        self.double_signed_area() > 0
+    }
+
+    /// The number of lattice points on the boundary, which for a polygon with only horizontal
+    /// and vertical edges is also the length of the boundary.
+    pub fn boundary_length(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| {
+                let dx = (b.x as i128 - a.x as i128).abs();
+                let dy = (b.y as i128 - a.y as i128).abs();
+                dx.gcd(&dy)
+            })
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside the polygon, by Pick's theorem.
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_length() + 2) / 2
+    }
+
+    /// The number of lattice points inside or on the polygon; for a dig plan, the number of
+    /// cells dug out.
+    pub fn enclosed_points(&self) -> i128 {
+        self.interior_points() + self.boundary_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIG_PLAN: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    fn direction(c: char) -> Direction {
+        match c {
+            'R' | '0' => Direction::East,
+            'D' | '1' => Direction::South,
+            'L' | '2' => Direction::West,
+            _ => Direction::North,
+        }
+    }
+
+    #[test]
+    fn test_dig_plan() {
+        let moves = DIG_PLAN.lines().map(|line| {
+            let mut parts = line.split(' ');
+            let d = direction(parts.next().unwrap().chars().next().unwrap());
+            (d, parts.next().unwrap().parse().unwrap())
+        });
+        let polygon = Polygon::from_moves(moves);
+        assert_eq!(polygon.vertices().len(), 14);
+        assert_eq!(polygon.boundary_length(), 38);
+        assert_eq!(polygon.area(), 42);
+        assert_eq!(polygon.enclosed_points(), 62);
+        assert!(polygon.is_clockwise());
+    }
+
+    #[test]
+    fn test_large_dig_plan() {
+        let moves = DIG_PLAN.lines().map(|line| {
+            let hex = &line[line.find('#').unwrap() + 1..line.len() - 1];
+            let length = i64::from_str_radix(&hex[..5], 16).unwrap();
+            (direction(hex.chars().nth(5).unwrap()), length)
+        });
+        let polygon = Polygon::from_moves(moves);
+        assert_eq!(polygon.enclosed_points(), 952408144115);
+    }
+
+    #[test]
+    fn test_vertices() {
+        let triangle = Polygon::from_vertices([
+            Position::new(0, 0),
+            Position::new(0, 3),
+            Position::new(4, 0),
+        ]);
+        assert!(!triangle.is_clockwise());
+        assert_eq!(triangle.double_area(), 12);
+        assert_eq!(triangle.area(), 6);
+        assert_eq!(triangle.boundary_length(), 8);
+        assert_eq!(triangle.interior_points(), 3);
+
+        let huge = i64::MAX / 2;
+        let square = Polygon::from_vertices([
+            Position::new(-huge, -huge),
+            Position::new(huge, -huge),
+            Position::new(huge, huge),
+            Position::new(-huge, huge),
+        ]);
+        assert!(square.is_clockwise());
+        assert_eq!(square.area(), (2 * huge as i128).pow(2));
+    }
+}
@@ -1,8 +1,15 @@
 #![allow(dead_code)]
 //use num_traits::Signed;
-use num_traits::{ConstOne, ConstZero, Num, Signed};
+use num_traits::{
+    CheckedAdd, ConstOne, ConstZero, Euclid, Num, SaturatingAdd, Signed, WrappingAdd,
+};
 use std::fmt::Debug;
-use std::ops::{Add, Sub};
+use thiserror::Error;
+
+use crate::grid::{self, Point, Vector};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
 pub struct Position<T: Num> {
@@ -52,7 +59,7 @@ impl<T: Num> Sub for Position<T> {
         }
     }
 }
-impl<T: Num + Copy> std::ops::Mul<T> for Position<T> {
+impl<T: Num + Copy> Mul<T> for Position<T> {
     type Output = Self;
     fn mul(self, other: T) -> Self {
         Position {
@@ -61,6 +68,104 @@ impl<T: Num + Copy> std::ops::Mul<T> for Position<T> {
         }
     }
 }
+impl<T: Num + Neg<Output = T>> Neg for Position<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Position {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+impl<T: Num + Copy> Div<T> for Position<T> {
+    type Output = Self;
+    fn div(self, other: T) -> Self {
+        Position {
+            x: self.x / other,
+            y: self.y / other,
+        }
+    }
+}
+// `%` gives the non-negative remainder, like `Vector`, so positions wrap onto a torus.
+impl<T: Num + Copy + Euclid> Rem<T> for Position<T> {
+    type Output = Self;
+    fn rem(self, other: T) -> Self {
+        self.rem_euclid(other)
+    }
+}
+impl<T: Num + Copy> AddAssign for Position<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+impl<T: Num + Copy> SubAssign for Position<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+impl<T: Num + Copy> MulAssign<T> for Position<T> {
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+impl<T: Num + Copy> DivAssign<T> for Position<T> {
+    fn div_assign(&mut self, other: T) {
+        *self = *self / other;
+    }
+}
+impl<T: Num + Copy + Euclid> RemAssign<T> for Position<T> {
+    fn rem_assign(&mut self, other: T) {
+        *self = *self % other;
+    }
+}
+
+impl<T: Num + Copy> Position<T> {
+    pub fn checked_add(self, other: Self) -> Option<Self>
+    where
+        T: CheckedAdd,
+    {
+        Some(Position {
+            x: self.x.checked_add(&other.x)?,
+            y: self.y.checked_add(&other.y)?,
+        })
+    }
+    pub fn saturating_add(self, other: Self) -> Self
+    where
+        T: SaturatingAdd,
+    {
+        Position {
+            x: self.x.saturating_add(&other.x),
+            y: self.y.saturating_add(&other.y),
+        }
+    }
+    pub fn wrapping_add(self, other: Self) -> Self
+    where
+        T: WrappingAdd,
+    {
+        Position {
+            x: self.x.wrapping_add(&other.x),
+            y: self.y.wrapping_add(&other.y),
+        }
+    }
+    /// The remainder of each coordinate that is never negative, e.g. for wrapping onto a torus.
+    pub fn rem_euclid(self, other: T) -> Self
+    where
+        T: Euclid,
+    {
+        Position {
+            x: self.x.rem_euclid(&other),
+            y: self.y.rem_euclid(&other),
+        }
+    }
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+    /// The z component of the 3D cross product, positive when `other` is clockwise from `self`
+    /// with `y` increasing downwards.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
 
 impl<T: Num + Copy + PartialOrd + ConstOne> Position<T> {
     fn abs_diff(a: T, b: T) -> T {
@@ -216,6 +321,119 @@ impl<T: Num + ConstOne + ConstZero> Position<T> {
     pub const DIRECTIONS: [Self; 3] = [Self::RIGHT, Self::DOWN, Self::WAIT];
 }
 
+impl From<grid::Direction> for Direction {
+    fn from(d: grid::Direction) -> Self {
+        match d {
+            grid::Direction::North => Direction::Up,
+            grid::Direction::East => Direction::Right,
+            grid::Direction::South => Direction::Down,
+            grid::Direction::West => Direction::Left,
+            grid::Direction::Wait => Direction::Wait,
+        }
+    }
+}
+
+impl From<Direction> for grid::Direction {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => grid::Direction::North,
+            Direction::Right => grid::Direction::East,
+            Direction::Down => grid::Direction::South,
+            Direction::Left => grid::Direction::West,
+            Direction::Wait => grid::Direction::Wait,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("coordinates out of range for the target type")]
+pub struct CoordinateRangeError;
+
+impl From<Position<usize>> for Point {
+    fn from(p: Position<usize>) -> Self {
+        Point::new(p.x, p.y)
+    }
+}
+
+impl From<Point> for Position<usize> {
+    fn from(p: Point) -> Self {
+        Position::new(p.x, p.y)
+    }
+}
+
+impl From<Position<isize>> for Vector {
+    fn from(p: Position<isize>) -> Self {
+        Vector::new(p.x, p.y)
+    }
+}
+
+impl From<Vector> for Position<isize> {
+    fn from(v: Vector) -> Self {
+        Position::new(v.x, v.y)
+    }
+}
+
+// `Point` and `Vector` convert losslessly with `Position<usize>` and `Position<isize>`; every
+// other pairing needs a range check, so gets `TryFrom` instead.
+macro_rules! point_conversions {
+    ($($t:ty),*) => {$(
+        impl TryFrom<Position<$t>> for Point {
+            type Error = CoordinateRangeError;
+            fn try_from(p: Position<$t>) -> Result<Self, Self::Error> {
+                Position::<usize>::new_try_from_position(p)
+                    .map(Point::from)
+                    .map_err(|_| CoordinateRangeError)
+            }
+        }
+
+        impl TryFrom<Point> for Position<$t> {
+            type Error = CoordinateRangeError;
+            fn try_from(p: Point) -> Result<Self, Self::Error> {
+                Position::new_try_from_position(Position::<usize>::from(p))
+                    .map_err(|_| CoordinateRangeError)
+            }
+        }
+    )*};
+}
+
+macro_rules! vector_conversions {
+    ($($t:ty),*) => {$(
+        impl TryFrom<Position<$t>> for Vector {
+            type Error = CoordinateRangeError;
+            fn try_from(p: Position<$t>) -> Result<Self, Self::Error> {
+                Position::<isize>::new_try_from_position(p)
+                    .map(Vector::from)
+                    .map_err(|_| CoordinateRangeError)
+            }
+        }
+
+        impl TryFrom<Vector> for Position<$t> {
+            type Error = CoordinateRangeError;
+            fn try_from(v: Vector) -> Result<Self, Self::Error> {
+                Position::new_try_from_position(Position::<isize>::from(v))
+                    .map_err(|_| CoordinateRangeError)
+            }
+        }
+    )*};
+}
+
+macro_rules! scalar_mul {
+    ($($t:ty),*) => {$(
+        impl Mul<Position<$t>> for $t {
+            type Output = Position<$t>;
+            fn mul(self, other: Position<$t>) -> Position<$t> {
+                other * self
+            }
+        }
+    )*};
+}
+
+point_conversions!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128);
+vector_conversions!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize);
+scalar_mul!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,4 +461,71 @@ mod tests {
         let p4: Position<usize> = Position::new_from_position(Position::<u16>::new(2, 2));
         println!("{:?}", p4);
     }
+    #[test]
+    fn test_operators() {
+        let mut p = Position::new(7i64, -3);
+        assert_eq!(-p, Position::new(-7, 3));
+        assert_eq!(p / 2, Position::new(3, -1));
+        assert_eq!(p % 4, Position::new(3, 1));
+        assert_eq!(p.rem_euclid(4), Position::new(3, 1));
+        assert_eq!(2 * p, p * 2);
+        p += Position::new(1, 1);
+        p -= Position::new(0, 2);
+        p *= 3;
+        assert_eq!(p, Position::new(24, -12));
+        p /= 4;
+        p %= 5;
+        assert_eq!(p, Position::new(1, 2));
+        assert_eq!(Position::new(7u8, 3) % 4, Position::new(3, 3));
+        assert_eq!(Position::new(1, 2).dot(Position::new(3, 4)), 11);
+        assert_eq!(Position::new(1, 0).cross(Position::new(0, 1)), 1);
+    }
+    #[test]
+    fn test_overflowing_adds() {
+        let p = Position::new(250u8, 3);
+        let q = Position::new(10u8, 1);
+        assert_eq!(p.checked_add(q), None);
+        assert_eq!(q.checked_add(q), Some(Position::new(20, 2)));
+        assert_eq!(p.saturating_add(q), Position::new(255, 4));
+        assert_eq!(p.wrapping_add(q), Position::new(4, 4));
+    }
+    #[test]
+    fn test_grid_conversions() {
+        let point = Point::new(3, 4);
+        assert_eq!(Position::<usize>::from(point), Position::new(3, 4));
+        assert_eq!(Position::<i32>::try_from(point), Ok(Position::new(3, 4)));
+        assert_eq!(Point::try_from(Position::new(3i64, 4)), Ok(point));
+        assert_eq!(
+            Point::try_from(Position::new(-1i32, 4)),
+            Err(CoordinateRangeError)
+        );
+        let vector = Vector::new(-3, 300);
+        assert_eq!(
+            Position::<i16>::try_from(vector),
+            Ok(Position::new(-3, 300))
+        );
+        assert!(Position::<i8>::try_from(vector).is_err());
+        assert!(Position::<u32>::try_from(vector).is_err());
+        assert_eq!(Vector::try_from(Position::new(-3i64, 300)), Ok(vector));
+        assert_eq!(Vector::from(Position::<isize>::from(vector)), vector);
+
+        assert_eq!(
+            Point::try_from(Position::<isize>::new(-1, 2)),
+            Err(CoordinateRangeError)
+        );
+        assert_eq!(Point::try_from(Position::<isize>::new(3, 4)), Ok(point));
+        assert_eq!(Position::<isize>::try_from(point), Ok(Position::new(3, 4)));
+        assert_eq!(
+            Vector::try_from(Position::<usize>::new(1, 2)),
+            Ok(Vector::new(1, 2))
+        );
+        assert_eq!(
+            Position::<usize>::try_from(vector),
+            Err(CoordinateRangeError)
+        );
+        assert_eq!(
+            Position::<usize>::try_from(Vector::new(3, 4)),
+            Ok(Position::new(3, 4))
+        );
+    }
 }
@@ -1,5 +1,7 @@
-use num_traits::{ConstOne, ConstZero, Num};
+use num_traits::{CheckedAdd, CheckedSub, ConstOne, ConstZero, Num};
 use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Neg;
 use std::ops::Sub;
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
@@ -58,3 +60,352 @@ impl<T: Num> Sub for Pos3<T> {
         }
     }
 }
+
+impl<T: Num + Copy> Mul<T> for Pos3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Pos3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T: Num + Neg<Output = T>> Neg for Pos3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Pos3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Pos3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    fn abs_diff(a: T, b: T) -> T {
+        if a > b { a - b } else { b - a }
+    }
+
+    fn partial_min(a: T, b: T) -> T {
+        if b < a { b } else { a }
+    }
+
+    fn partial_max(a: T, b: T) -> T {
+        if b > a { b } else { a }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        Self::abs_diff(self.x, other.x)
+            + Self::abs_diff(self.y, other.y)
+            + Self::abs_diff(self.z, other.z)
+    }
+
+    /// The number of king's moves between the points, the largest difference on any axis.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let d = Self::partial_max(
+            Self::abs_diff(self.x, other.x),
+            Self::abs_diff(self.y, other.y),
+        );
+        Self::partial_max(d, Self::abs_diff(self.z, other.z))
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Pos3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// The component-wise minimum.
+    pub fn min(&self, other: &Self) -> Self {
+        Pos3 {
+            x: Self::partial_min(self.x, other.x),
+            y: Self::partial_min(self.y, other.y),
+            z: Self::partial_min(self.z, other.z),
+        }
+    }
+
+    /// The component-wise maximum.
+    pub fn max(&self, other: &Self) -> Self {
+        Pos3 {
+            x: Self::partial_max(self.x, other.x),
+            y: Self::partial_max(self.y, other.y),
+            z: Self::partial_max(self.z, other.z),
+        }
+    }
+
+    /// The smallest box containing all the points, as its minimum and maximum corners.
+    pub fn bounds(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((min.min(&p), max.max(&p))),
+        })
+    }
+}
+
+/// Unit offsets to the neighbouring cubes: the 6 sharing a face, then the 12 sharing only an
+/// edge, then the 8 sharing only a corner.
+const NEIGHBOR_OFFSETS: [[i8; 3]; 26] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+    [1, 1, 0],
+    [1, -1, 0],
+    [-1, 1, 0],
+    [-1, -1, 0],
+    [1, 0, 1],
+    [1, 0, -1],
+    [-1, 0, 1],
+    [-1, 0, -1],
+    [0, 1, 1],
+    [0, 1, -1],
+    [0, -1, 1],
+    [0, -1, -1],
+    [1, 1, 1],
+    [1, 1, -1],
+    [1, -1, 1],
+    [1, -1, -1],
+    [-1, 1, 1],
+    [-1, 1, -1],
+    [-1, -1, 1],
+    [-1, -1, -1],
+];
+
+/// The neighbours of a point, skipping any that would overflow `T`, so an unsigned point on
+/// an axis plane has fewer neighbours.
+pub struct Neighbors3<T: Num> {
+    center: Pos3<T>,
+    current_offset: usize,
+    offsets: &'static [[i8; 3]],
+}
+
+fn offset_coordinate<T: Num + Copy + ConstOne + CheckedAdd + CheckedSub>(
+    value: T,
+    offset: i8,
+) -> Option<T> {
+    match offset {
+        1 => value.checked_add(&T::ONE),
+        -1 => value.checked_sub(&T::ONE),
+        _ => Some(value),
+    }
+}
+
+fn offset<T: Num + Copy + ConstOne + CheckedAdd + CheckedSub>(
+    p: Pos3<T>,
+    [dx, dy, dz]: [i8; 3],
+) -> Option<Pos3<T>> {
+    Some(Pos3 {
+        x: offset_coordinate(p.x, dx)?,
+        y: offset_coordinate(p.y, dy)?,
+        z: offset_coordinate(p.z, dz)?,
+    })
+}
+
+impl<T: Num + Copy + ConstOne + CheckedAdd + CheckedSub> Iterator for Neighbors3<T> {
+    type Item = Pos3<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&[dx, dy, dz]) = self.offsets.get(self.current_offset) {
+            self.current_offset += 1;
+            let neighbor = offset(self.center, [dx, dy, dz]);
+            if neighbor.is_some() {
+                return neighbor;
+            }
+        }
+        None
+    }
+}
+
+impl<T: Num + Copy + ConstOne + CheckedAdd + CheckedSub> Pos3<T> {
+    fn neighbors(self, count: usize) -> Neighbors3<T> {
+        Neighbors3 {
+            center: self,
+            current_offset: 0,
+            offsets: &NEIGHBOR_OFFSETS[..count],
+        }
+    }
+
+    /// The neighbours sharing a face.
+    pub fn neighbors6(self) -> Neighbors3<T> {
+        self.neighbors(6)
+    }
+
+    /// The neighbours sharing a face or an edge.
+    pub fn neighbors18(self) -> Neighbors3<T> {
+        self.neighbors(18)
+    }
+
+    /// The neighbours sharing a face, an edge or a corner.
+    pub fn neighbors26(self) -> Neighbors3<T> {
+        self.neighbors(26)
+    }
+}
+
+/// One of the 24 rotations that take the axes onto the axes, stored as a matrix whose rows
+/// give the new `x`, `y` and `z` in terms of the old ones.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Rotation {
+    matrix: [[i8; 3]; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Every proper rotation, starting with the identity.  Each is a permutation of the axes
+    /// with signs chosen so the determinant is 1.
+    pub fn all() -> [Rotation; 24] {
+        const PERMUTATIONS: [([usize; 3], i8); 6] = [
+            ([0, 1, 2], 1),
+            ([0, 2, 1], -1),
+            ([1, 0, 2], -1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([2, 1, 0], -1),
+        ];
+        let mut rotations = [Self::IDENTITY; 24];
+        let mut count = 0;
+        for (permutation, parity) in PERMUTATIONS {
+            for signs in 0..8 {
+                let sign = |axis: usize| if signs >> axis & 1 == 0 { 1 } else { -1 };
+                if sign(0) * sign(1) * sign(2) != parity {
+                    continue;
+                }
+                let mut matrix = [[0; 3]; 3];
+                for (row, &column) in permutation.iter().enumerate() {
+                    matrix[row][column] = sign(row);
+                }
+                rotations[count] = Rotation { matrix };
+                count += 1;
+            }
+        }
+        rotations
+    }
+
+    pub fn matrix(&self) -> [[i8; 3]; 3] {
+        self.matrix
+    }
+
+    pub fn apply<T: Num + Copy + Neg<Output = T>>(&self, p: Pos3<T>) -> Pos3<T> {
+        let term = |entry: i8, value: T| match entry {
+            1 => value,
+            -1 => -value,
+            _ => T::zero(),
+        };
+        let row = |r: [i8; 3]| term(r[0], p.x) + term(r[1], p.y) + term(r[2], p.z);
+        Pos3 {
+            x: row(self.matrix[0]),
+            y: row(self.matrix[1]),
+            z: row(self.matrix[2]),
+        }
+    }
+
+    /// The rotation that applies `first` and then `self`.
+    pub fn after(&self, first: &Rotation) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = (0..3).map(|k| self.matrix[i][k] * first.matrix[k][j]).sum();
+            }
+        }
+        Rotation { matrix }
+    }
+
+    /// The inverse rotation, which for a rotation matrix is its transpose.
+    pub fn inverse(&self) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = self.matrix[j][i];
+            }
+        }
+        Rotation { matrix }
+    }
+}
+
+impl<T: Num + Copy + Neg<Output = T>> Pos3<T> {
+    pub fn rotate(self, rotation: &Rotation) -> Self {
+        rotation.apply(self)
+    }
+
+    /// The point under each of the 24 rotations, in the order of `Rotation::all`.
+    pub fn rotations(self) -> impl Iterator<Item = Self> {
+        Rotation::all().into_iter().map(move |r| r.apply(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Pos3::new(1, -2, 3);
+        let b = Pos3::new(4, 0, -1);
+        assert_eq!(a * 2, Pos3::new(2, -4, 6));
+        assert_eq!(-a, Pos3::new(-1, 2, -3));
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.dot(&b), 1);
+        assert_eq!(Pos3::<i32>::UX.cross(&Pos3::UY), Pos3::UZ);
+        assert_eq!(a.cross(&b).dot(&a), 0);
+        assert_eq!(
+            Pos3::bounds([a, b, Pos3::new(0, 5, 0)]),
+            Some((Pos3::new(0, -2, -1), Pos3::new(4, 5, 3)))
+        );
+        assert_eq!(Pos3::<i32>::bounds([]), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Pos3::new(5i64, 5, 5);
+        assert_eq!(p.neighbors6().count(), 6);
+        assert_eq!(p.neighbors18().count(), 18);
+        let all: HashSet<_> = p.neighbors26().collect();
+        assert_eq!(all.len(), 26);
+        assert!(all.iter().all(|n| n.chebyshev(&p) == 1));
+        assert!(p.neighbors6().all(|n| n.manhattan(&p) == 1));
+        assert!(p.neighbors18().all(|n| n.manhattan(&p) <= 2));
+
+        let origin = Pos3::new(0usize, 0, 0);
+        assert_eq!(origin.neighbors6().count(), 3);
+        assert_eq!(origin.neighbors26().count(), 7);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+        for r in rotations {
+            assert_eq!(r.after(&r.inverse()), Rotation::IDENTITY);
+            for s in rotations {
+                assert!(rotations.contains(&r.after(&s)));
+            }
+            let (x, y) = (Pos3::<i32>::UX.rotate(&r), Pos3::UY.rotate(&r));
+            assert_eq!(x.cross(&y), Pos3::UZ.rotate(&r));
+        }
+        let p = Pos3::new(1, 2, 3);
+        let images: HashSet<_> = p.rotations().collect();
+        assert_eq!(images.len(), 24);
+        assert!(images.iter().all(|q| q.dot(q) == 14));
+    }
+}